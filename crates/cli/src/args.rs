@@ -6,8 +6,9 @@ use clap::{command, Parser};
 use clap_verbosity_flag::Verbosity;
 
 pub use self::input_query::InputQuery;
-use self::{input_data::InputData, output::Output};
+use self::{apply_options::ApplyOptions, input_data::InputData, output::Output};
 
+pub mod apply_options;
 pub mod input_data;
 pub mod input_query;
 pub mod output;
@@ -27,6 +28,9 @@ pub struct Args {
     #[clap(flatten)]
     pub input_query: InputQuery,
 
+    #[clap(flatten)]
+    pub apply_options: ApplyOptions,
+
     #[clap(flatten)]
     pub output: Output,
 
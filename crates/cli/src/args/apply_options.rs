@@ -0,0 +1,61 @@
+use clap::{Args, ValueEnum};
+use gq_core::query::{apply, query_operators};
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum OutOfBounds {
+    Error,
+    Null,
+    Clamp,
+}
+
+impl From<OutOfBounds> for query_operators::OutOfBounds {
+    fn from(out_of_bounds: OutOfBounds) -> Self {
+        match out_of_bounds {
+            OutOfBounds::Error => query_operators::OutOfBounds::Error,
+            OutOfBounds::Null => query_operators::OutOfBounds::Null,
+            OutOfBounds::Clamp => query_operators::OutOfBounds::Clamp,
+        }
+    }
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum EmptyElements {
+    Remove,
+    Keep,
+    Null,
+}
+
+impl From<EmptyElements> for apply::EmptyElements {
+    fn from(empty_elements: EmptyElements) -> Self {
+        match empty_elements {
+            EmptyElements::Remove => apply::EmptyElements::Remove,
+            EmptyElements::Keep => apply::EmptyElements::Keep,
+            EmptyElements::Null => apply::EmptyElements::Null,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct ApplyOptions {
+    /// Behaviour when an index or range is out of the bounds of an array
+    #[clap(long, default_value_t = OutOfBounds::Error)]
+    #[arg(value_enum)]
+    pub out_of_bounds: OutOfBounds,
+    /// Behaviour for the elements of arrays that are empty objects or arrays after applying the query
+    #[clap(long, default_value_t = EmptyElements::Remove)]
+    #[arg(value_enum)]
+    pub empty_elements: EmptyElements,
+    /// Fail on the first error inside an array instead of discarding the element that caused it
+    #[clap(long)]
+    pub strict: bool,
+}
+
+impl From<ApplyOptions> for apply::ApplyOptions {
+    fn from(apply_options: ApplyOptions) -> Self {
+        Self {
+            out_of_bounds: apply_options.out_of_bounds.into(),
+            empty_elements: apply_options.empty_elements.into(),
+            strict: apply_options.strict,
+        }
+    }
+}
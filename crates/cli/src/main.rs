@@ -1,7 +1,12 @@
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use clap::Parser;
 use gq_cli::args::Args;
-use gq_core::{data::Data, query::Query};
+use gq_core::{
+    data::Data,
+    query::{diagnostic::Diagnostic, Query},
+};
 use serde_json::Value;
 
 fn main() -> Result<()> {
@@ -16,9 +21,32 @@ fn main() -> Result<()> {
 
     let query = input_query.parse::<Query>()?;
     let value = Value::try_from(&input_data)?;
-    let result = query.apply(value)?;
+    let output = query.apply_with_diagnostics(value, args.apply_options.into())?;
 
-    args.output.write_value(&result, *input_data.data_type())?;
+    args.output
+        .write_value(&output.value, *input_data.data_type())?;
+    print_warnings_summary(&output.diagnostics);
 
     Ok(())
 }
+
+/// Prints how many warnings of each kind were found while applying the query. The warnings
+/// themselves are logged with `-v`.
+fn print_warnings_summary(diagnostics: &[Diagnostic]) {
+    let mut kind_counts = BTreeMap::new();
+    for diagnostic in diagnostics {
+        if diagnostic.kind().level() <= log::Level::Warn {
+            *kind_counts.entry(*diagnostic.kind()).or_insert(0) += 1;
+        }
+    }
+    if kind_counts.is_empty() {
+        return;
+    }
+
+    let total = kind_counts.values().sum::<usize>();
+    let plural = if total == 1 { "" } else { "s" };
+    eprintln!("warning: the query was applied with {total} warning{plural} (use -v to see them)");
+    for (kind, count) in kind_counts {
+        eprintln!("  {kind}: {count}");
+    }
+}
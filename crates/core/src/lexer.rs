@@ -29,6 +29,18 @@ pub enum Token {
     RBracket,
     #[token(".")]
     Dot,
+    #[token("..")]
+    DoubleDot,
+    #[token("*")]
+    Star,
+    #[token("**")]
+    DoubleStar,
+    #[token("?")]
+    Question,
+    #[token("@")]
+    At,
+    #[token("??")]
+    DoubleQuestion,
     #[token(":")]
     Colon,
     #[token(",")]
@@ -49,6 +61,16 @@ pub enum Token {
     Tilde,
     #[token("!~")]
     NotTilde,
+    #[token("^=")]
+    CaretEqual,
+    #[token("$=")]
+    DollarEqual,
+    #[token("*=")]
+    StarEqual,
+    #[token("||")]
+    Or,
+    #[token("!")]
+    Not,
     // This regex does not support keys starting with '-' or numbers
     #[regex(r"[a-zA-Z_][\w-]*", |lex| lex.slice().to_string())]
     Identifier(String),
@@ -92,6 +114,12 @@ impl Display for Token {
             Token::LBracket => '['.fmt(f),
             Token::RBracket => ']'.fmt(f),
             Token::Dot => '.'.fmt(f),
+            Token::DoubleDot => "..".fmt(f),
+            Token::Star => '*'.fmt(f),
+            Token::DoubleStar => "**".fmt(f),
+            Token::Question => '?'.fmt(f),
+            Token::At => '@'.fmt(f),
+            Token::DoubleQuestion => "??".fmt(f),
             Token::Colon => ':'.fmt(f),
             Token::Comma => ','.fmt(f),
             Token::Equal => '='.fmt(f),
@@ -102,6 +130,11 @@ impl Display for Token {
             Token::LessEqual => "<=".fmt(f),
             Token::Tilde => '~'.fmt(f),
             Token::NotTilde => "!~".fmt(f),
+            Token::CaretEqual => "^=".fmt(f),
+            Token::DollarEqual => "$=".fmt(f),
+            Token::StarEqual => "*=".fmt(f),
+            Token::Or => "||".fmt(f),
+            Token::Not => '!'.fmt(f),
             Token::Identifier(key) => key.fmt(f),
             Token::Bool(b) => b.fmt(f),
             Token::PosInteger(n) => n.fmt(f),
@@ -143,6 +176,12 @@ mod tests {
     #[case::l_bracket("[", Token::LBracket)]
     #[case::r_bracket("]", Token::RBracket)]
     #[case::dot(".", Token::Dot)]
+    #[case::double_dot("..", Token::DoubleDot)]
+    #[case::star("*", Token::Star)]
+    #[case::double_star("**", Token::DoubleStar)]
+    #[case::question("?", Token::Question)]
+    #[case::at("@", Token::At)]
+    #[case::double_question("??", Token::DoubleQuestion)]
     #[case::colon(":", Token::Colon)]
     #[case::comma(",", Token::Comma)]
     #[case::equal("=", Token::Equal)]
@@ -153,6 +192,11 @@ mod tests {
     #[case::less_equal("<=", Token::LessEqual)]
     #[case::tilde("~", Token::Tilde)]
     #[case::not_tilde("!~", Token::NotTilde)]
+    #[case::caret_equal("^=", Token::CaretEqual)]
+    #[case::dollar_equal("$=", Token::DollarEqual)]
+    #[case::star_equal("*=", Token::StarEqual)]
+    #[case::or("||", Token::Or)]
+    #[case::not("!", Token::Not)]
     #[case::true_token("true", Token::Bool(true))]
     #[case::false_token("false", Token::Bool(false))]
     #[case::null("null", Token::Null)]
@@ -225,6 +269,12 @@ mod tests {
     #[case::with_dash_and_underscore("key-with-dash_and_underscore")]
     #[case::with_caps("KeyWithCaps")]
     #[case::starting_with_underscore("_key")]
+    #[case::starting_with_keyword("order")]
+    #[case::starting_with_keyword_and_dash("not-found")]
+    #[case::starting_with_quantifier("allowed")]
+    #[case::or_word("or")]
+    #[case::not_word("not")]
+    #[case::in_word("in")]
     fn identifier_parses(#[case] input: &str) {
         let expected = Token::Identifier(input.to_string());
         assert_next_token(input, expected);
@@ -272,6 +322,24 @@ mod tests {
         assert!(matches!(token, Err(Error::UnknownCharacter)));
     }
 
+    #[rstest]
+    #[case::range("1..2", vec![Token::PosInteger(1), Token::DoubleDot, Token::PosInteger(2)])]
+    #[case::negative_range(
+        "-3..-1",
+        vec![Token::NegInteger(-3), Token::DoubleDot, Token::NegInteger(-1)]
+    )]
+    #[case::open_range("5..", vec![Token::PosInteger(5), Token::DoubleDot])]
+    #[case::exclusive_range(
+        "0..<10",
+        vec![Token::PosInteger(0), Token::DoubleDot, Token::Less, Token::PosInteger(10)]
+    )]
+    fn range_parses(#[case] input: &str, #[case] expected: Vec<Token>) {
+        let tokens = Token::lexer(input)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("Error parsing tokens");
+        assert_eq!(tokens, expected);
+    }
+
     // TODO: Add more tests for various consecutive tokens. For example,
     // Two consecutive strings, two consecutive floats, etc.
 }
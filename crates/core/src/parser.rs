@@ -1,14 +1,19 @@
 use crate::lexer::{self, Token};
 use crate::query::query_arguments::{
-    Number, QueryArgument, QueryArgumentOperation, QueryArgumentValue, QueryArguments,
+    ComparisonOperator, Number, Quantifier, QueryArgument, QueryArgumentExpression,
+    QueryArgumentOperation, QueryArgumentRhs, QueryArgumentValue, QueryArguments, RegexPattern,
+    StringPattern,
+};
+use crate::query::query_key::{AtomicQueryKey, KeyPattern, QueryKey, RawKey};
+use crate::query::query_operators::{
+    Aggregation, IndexingRange, IndexingValue, QueryOperator, QueryOperators, SortKey, SortOrder,
 };
-use crate::query::query_key::{AtomicQueryKey, QueryKey, RawKey};
-use crate::query::query_operators::{IndexingValue, QueryOperator, QueryOperators};
 use crate::query::{ChildQuery, ChildQueryBuilder, Query, QueryBuilder};
 use logos::{Logos, Span, SpannedIter};
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
 use std::iter::Peekable;
-use std::ops::RangeInclusive;
+use std::num::{NonZeroIsize, TryFromIntError};
+use std::ops::Bound;
 use std::str::FromStr;
 use thiserror::Error;
 
@@ -31,6 +36,14 @@ pub enum Error {
     Construction(crate::query::Error, Span),
     #[error("Regex parsing error: {0}")]
     Regex(regex::Error, Span),
+    #[error("Unknown regex flag '{0}'")]
+    RegexFlag(char, Span),
+    #[error("Integer parsing error: {0}")]
+    Integer(TryFromIntError, Span),
+    #[error("Step cannot be zero")]
+    ZeroStep(Span),
+    #[error("Unknown operator '{0}'")]
+    UnknownOperator(String, Span),
 }
 
 impl Error {
@@ -42,6 +55,10 @@ impl Error {
             Self::Lexer(_, span) => span,
             Self::Construction(_, span) => span,
             Self::Regex(_, span) => span,
+            Self::RegexFlag(_, span) => span,
+            Self::Integer(_, span) => span,
+            Self::ZeroStep(span) => span,
+            Self::UnknownOperator(_, span) => span,
         }
     }
 }
@@ -49,6 +66,11 @@ impl Error {
 pub struct Parser<'src> {
     lexer: Peekable<SpannedIter<'src, Token>>,
     source: &'src str,
+    /// Span of the last consumed token
+    previous_span: Span,
+    /// Token to output before the ones of the lexer, which is the remainder of a token that was
+    /// split in two, as `*=` after a wildcard key
+    split_token: Option<SpannedToken>,
 }
 
 impl<'src> Parser<'src> {
@@ -56,13 +78,15 @@ impl<'src> Parser<'src> {
         Self {
             lexer: Token::lexer(source).spanned().peekable(),
             source,
+            previous_span: 0..0,
+            split_token: None,
         }
     }
 
     pub fn parse(&mut self) -> Result<Query> {
         let query = self.parse_root_query()?;
 
-        if self.lexer.next().is_some() {
+        if self.split_token.is_some() || self.lexer.next().is_some() {
             // TODO: use this error or the generic one?
             return Err(Error::UnexpectedTokenAfterRootQuery(self.last_span()));
         }
@@ -82,7 +106,15 @@ impl<'src> Parser<'src> {
         start.start..end.end
     }
 
+    /// Span from the start of the given span to the end of the last consumed token
+    fn span_from(&self, start: Span) -> Span {
+        Self::span_between(start, self.previous_span.clone())
+    }
+
     fn peek(&mut self) -> Result<SpannedTokenRef<'_>> {
+        if let Some((token, span)) = &self.split_token {
+            return Ok((token, span.clone()));
+        }
         match self.lexer.peek() {
             Some((token, span)) => {
                 let token = token
@@ -94,11 +126,39 @@ impl<'src> Parser<'src> {
         }
     }
 
+    /// Peeks the `n`-th token after the next one, which tells contextual keywords apart from keys
+    fn peek_nth(&self, n: usize) -> Option<Token> {
+        let n = match (&self.split_token, n) {
+            (Some((token, _)), 0) => return Some(token.clone()),
+            (Some(_), n) => n - 1,
+            (None, n) => n,
+        };
+        let mut lexer = self.lexer.clone();
+        lexer.nth(n).and_then(|(token, _)| token.ok())
+    }
+
+    /// Whether the next word is a keyword followed by a key. Words followed by the `in` or
+    /// `not in` operators are keys themselves, as in `not in [1]` or `all not in [1]`.
+    fn is_keyword_before_key(&self) -> bool {
+        match self.peek_nth(1) {
+            Some(Token::Identifier(word)) if word == "in" => false,
+            Some(Token::Identifier(word)) if word == "not" => {
+                !matches!(self.peek_nth(2), Some(Token::Identifier(word)) if word == "in")
+            }
+            Some(token) => starts_argument_key(&token),
+            None => false,
+        }
+    }
+
     fn consume(&mut self) -> Result<Span> {
         self.next_token().map(|(_, span)| span)
     }
 
     fn next_token(&mut self) -> Result<SpannedToken> {
+        if let Some((token, span)) = self.split_token.take() {
+            self.previous_span = span.clone();
+            return Ok((token, span));
+        }
         let spanned_token = self
             .lexer
             .next()
@@ -107,6 +167,7 @@ impl<'src> Parser<'src> {
 
         let (token, span) = spanned_token;
         let token = token.map_err(|err| Error::Lexer(err, span.clone()))?;
+        self.previous_span = span.clone();
         Ok((token, span))
     }
 
@@ -162,11 +223,12 @@ impl<'src> Parser<'src> {
     }
 
     /// # Grammar
-    /// `QUERY -> QUERY_KEY QUERY_ALIAS | QUERY_KEY QUERY_ALIAS { QUERY_CONTENT }
+    /// `QUERY -> QUERY_KEY QUERY_ALIAS QUERY_DEFAULT | QUERY_KEY QUERY_ALIAS QUERY_DEFAULT { QUERY_CONTENT }
     fn parse_query(&mut self) -> Result<ChildQuery> {
         let query_span_start = self.current_span()?;
         let query_key = self.parse_query_key()?;
         let query_alias = self.parse_query_alias()?;
+        let query_default = self.parse_query_default()?;
 
         match self.peek()? {
             (Token::LBrace, _) => {
@@ -178,6 +240,7 @@ impl<'src> Parser<'src> {
                 ChildQueryBuilder::default()
                     .key(query_key)
                     .alias(query_alias)
+                    .default_value(query_default)
                     .children(children)
                     .build()
                     .map_err(|err| Error::Construction(err.into(), query_span))
@@ -187,6 +250,7 @@ impl<'src> Parser<'src> {
                 ChildQueryBuilder::default()
                     .key(query_key)
                     .alias(query_alias)
+                    .default_value(query_default)
                     .build()
                     // TODO: We should take the end span from the query alias function
                     .map_err(|err| Error::Construction(err.into(), query_span))
@@ -199,16 +263,39 @@ impl<'src> Parser<'src> {
     fn parse_root_query_key(&mut self) -> Result<QueryKey> {
         match self.peek()? {
             // We have to know what comes next due to the epsilon rule
-            (Token::Identifier(_), _) | (Token::String(_), _) => self.parse_query_key(),
+            (Token::Identifier(_), _)
+            | (Token::String(_), _)
+            | (Token::Star, _)
+            | (Token::Tilde, _)
+            | (Token::DoubleStar, _) => self.parse_query_key(),
             _ => Ok(Default::default()),
         }
     }
 
     /// # Grammar
-    /// `QUERY_KEY -> ATOMIC_QUERY_KEY . QUERY_KEY | ATOMIC_QUERY_KEY`
+    /// `QUERY_KEY -> ATOMIC_QUERY_KEY . QUERY_KEY | ** . QUERY_KEY | ATOMIC_QUERY_KEY`
     fn parse_query_key(&mut self) -> Result<QueryKey> {
         let mut keys = Vec::new();
         loop {
+            if let (Token::DoubleStar, _) = self.peek()? {
+                let descendants_span = self.consume()?;
+                // Descendants must be followed by the keys to look up
+                match self.next_token()? {
+                    (Token::Dot, _) => (),
+                    (unexpected_token, span) => {
+                        return Err(Error::UnexpectedToken(unexpected_token, span))
+                    }
+                };
+                keys.push(AtomicQueryKey::new(
+                    RawKey::Descendants,
+                    false,
+                    QueryArguments::default(),
+                    QueryOperators::default(),
+                    descendants_span,
+                ));
+                continue;
+            }
+
             let atomic_query_key = self.parse_atomic_query_key()?;
             keys.push(atomic_query_key);
             match self.peek()? {
@@ -221,31 +308,78 @@ impl<'src> Parser<'src> {
     }
 
     /// # Grammar
-    /// `ATOMIC_QUERY_KEY -> RAW_KEY QUERY_ARGUMENTS QUERY_OPERATOR`
+    /// `ATOMIC_QUERY_KEY -> RAW_KEY OPTIONAL QUERY_ARGUMENTS QUERY_OPERATOR`
     fn parse_atomic_query_key(&mut self) -> Result<AtomicQueryKey> {
+        let key_span_start = self.current_span()?;
         let raw_key = self.parse_raw_key()?;
+        let optional = self.parse_optional()?;
         let arguments = self.parse_query_arguments()?;
         let query_operators = self.parse_query_operators()?;
-        Ok(AtomicQueryKey::new(raw_key, arguments, query_operators))
+        Ok(AtomicQueryKey::new(
+            raw_key,
+            optional,
+            arguments,
+            query_operators,
+            self.span_from(key_span_start),
+        ))
+    }
+
+    /// # Grammar
+    /// `OPTIONAL -> ? | ε`
+    fn parse_optional(&mut self) -> Result<bool> {
+        match self.peek()? {
+            (Token::Question, _) => {
+                self.consume()?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
     }
 
     /// # Grammar
-    /// `RAW_KEY -> key | string`
+    /// `RAW_KEY -> key | string | * | ~ REGEX`
     fn parse_raw_key(&mut self) -> Result<RawKey> {
         match self.next_token()? {
             (Token::Identifier(key), _) => Ok(RawKey::Identifier(key)),
             (Token::String(key), _) => Ok(RawKey::String(key)),
+            (Token::Star, _) => Ok(RawKey::Wildcard),
+            // The lexer reads `*=` as the contains operator, but where a key is expected it can
+            // only be a wildcard followed by the equal operator, as in `items(tags.*="x")`
+            (Token::StarEqual, span) => {
+                self.split_token = Some((Token::Equal, span.start + 1..span.end));
+                self.previous_span = span.start..span.start + 1;
+                Ok(RawKey::Wildcard)
+            }
+            (Token::Tilde, _) => self
+                .parse_regex()
+                .map(|regex| RawKey::Pattern(KeyPattern(regex))),
             (unexpected_token, span) => Err(Error::UnexpectedToken(unexpected_token, span)),
         }
     }
 
     /// # Grammar
-    /// `QUERY_ALIAS -> : RAW_KEY | ε`
+    /// `QUERY_ALIAS -> : key | : string | ε`
     fn parse_query_alias(&mut self) -> Result<Option<RawKey>> {
         match self.peek()? {
             (Token::Colon, _) => {
                 self.consume()?;
-                self.parse_raw_key().map(Some)
+                match self.next_token()? {
+                    (Token::Identifier(alias), _) => Ok(Some(RawKey::Identifier(alias))),
+                    (Token::String(alias), _) => Ok(Some(RawKey::String(alias))),
+                    (unexpected_token, span) => Err(Error::UnexpectedToken(unexpected_token, span)),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// # Grammar
+    /// `QUERY_DEFAULT -> ?? QUERY_ARGUMENT_VALUE | ε`
+    fn parse_query_default(&mut self) -> Result<Option<QueryArgumentValue>> {
+        match self.peek()? {
+            (Token::DoubleQuestion, _) => {
+                self.consume()?;
+                self.parse_query_argument_value().map(Some)
             }
             _ => Ok(None),
         }
@@ -281,13 +415,15 @@ impl<'src> Parser<'src> {
     }
 
     /// # Grammar
-    /// `QUERY_OPERATOR -> [INDEX] | ε
+    /// `QUERY_OPERATOR -> [INDEX] | [NAMED_OPERATOR] | ε
     fn parse_query_operator(&mut self) -> Result<Option<QueryOperator>> {
         match self.peek()? {
             (Token::LBracket, _) => {
                 self.consume()?;
-                let index = self.parse_index()?;
-                let query_operator = QueryOperator::Indexing(index);
+                let query_operator = match self.peek()? {
+                    (Token::Identifier(_), _) => self.parse_named_operator()?,
+                    _ => QueryOperator::Indexing(self.parse_index()?),
+                };
                 match self.next_token()? {
                     (Token::RBracket, _) => Ok(Some(query_operator)),
                     (unexpected_token, span) => Err(Error::UnexpectedToken(unexpected_token, span)),
@@ -298,12 +434,103 @@ impl<'src> Parser<'src> {
     }
 
     /// # Grammar
-    /// `QUERY_ARGUMENTS_CONTENT -> QUERY_ARGUMENT , QUERY_ARGUMENTS_CONTENT | QUERY_ARGUMENT`
-    fn parse_query_arguments_content(&mut self) -> Result<Vec<QueryArgument>> {
+    /// `NAMED_OPERATOR -> sort SORT_KEYS | count | sum | avg | min | max | groupBy OPERATOR_KEY | unique | uniqueBy OPERATOR_KEY | flatten FLATTEN_DEPTH`
+    fn parse_named_operator(&mut self) -> Result<QueryOperator> {
+        match self.next_token()? {
+            (Token::Identifier(name), span) => match name.as_str() {
+                "sort" => self.parse_sort_keys().map(QueryOperator::Sort),
+                "count" => Ok(QueryOperator::Aggregation(Aggregation::Count)),
+                "sum" => Ok(QueryOperator::Aggregation(Aggregation::Sum)),
+                "avg" => Ok(QueryOperator::Aggregation(Aggregation::Avg)),
+                "min" => Ok(QueryOperator::Aggregation(Aggregation::Min)),
+                "max" => Ok(QueryOperator::Aggregation(Aggregation::Max)),
+                "groupBy" => self.parse_operator_key().map(QueryOperator::GroupBy),
+                "unique" => Ok(QueryOperator::Unique(None)),
+                "uniqueBy" => self
+                    .parse_operator_key()
+                    .map(|unique_key| QueryOperator::Unique(Some(unique_key))),
+                "flatten" => self.parse_flatten_depth().map(QueryOperator::Flatten),
+                _ => Err(Error::UnknownOperator(name, span)),
+            },
+            (unexpected_token, span) => Err(Error::UnexpectedToken(unexpected_token, span)),
+        }
+    }
+
+    /// # Grammar
+    /// `OPERATOR_KEY -> ( QUERY_KEY )`
+    fn parse_operator_key(&mut self) -> Result<QueryKey> {
+        match self.next_token()? {
+            (Token::LParen, _) => (),
+            (unexpected_token, span) => return Err(Error::UnexpectedToken(unexpected_token, span)),
+        };
+        let operator_key = self.parse_query_key()?;
+        match self.next_token()? {
+            (Token::RParen, _) => Ok(operator_key),
+            (unexpected_token, span) => Err(Error::UnexpectedToken(unexpected_token, span)),
+        }
+    }
+
+    /// # Grammar
+    /// `FLATTEN_DEPTH -> ( POS_INTEGER ) | ε`
+    fn parse_flatten_depth(&mut self) -> Result<usize> {
+        match self.peek()? {
+            (Token::LParen, _) => self.consume()?,
+            _ => return Ok(QueryOperator::DEFAULT_FLATTEN_DEPTH),
+        };
+        let depth = match self.next_token()? {
+            (Token::PosInteger(value), span) => {
+                usize::try_from(value).map_err(|err| Error::Integer(err, span))?
+            }
+            (unexpected_token, span) => return Err(Error::UnexpectedToken(unexpected_token, span)),
+        };
+        match self.next_token()? {
+            (Token::RParen, _) => Ok(depth),
+            (unexpected_token, span) => Err(Error::UnexpectedToken(unexpected_token, span)),
+        }
+    }
+
+    /// # Grammar
+    /// `SORT_KEYS -> ( SORT_KEYS_CONTENT ) | ε`
+    /// `SORT_KEYS_CONTENT -> SORT_KEY , SORT_KEYS_CONTENT | SORT_KEY`
+    fn parse_sort_keys(&mut self) -> Result<Vec<SortKey>> {
+        match self.peek()? {
+            (Token::LParen, _) => self.consume()?,
+            _ => return Ok(Vec::new()),
+        };
+
+        let mut sort_keys = Vec::new();
+        loop {
+            sort_keys.push(self.parse_sort_key()?);
+            match self.next_token()? {
+                (Token::Comma, _) => (),
+                (Token::RParen, _) => return Ok(sort_keys),
+                (unexpected_token, span) => {
+                    return Err(Error::UnexpectedToken(unexpected_token, span))
+                }
+            }
+        }
+    }
+
+    /// # Grammar
+    /// `SORT_KEY -> QUERY_KEY asc | QUERY_KEY desc | QUERY_KEY`
+    fn parse_sort_key(&mut self) -> Result<SortKey> {
+        let key = self.parse_query_key()?;
+        let order = match self.peek()? {
+            (Token::Identifier(order), _) if order == "asc" => SortOrder::Ascending,
+            (Token::Identifier(order), _) if order == "desc" => SortOrder::Descending,
+            _ => return Ok(SortKey::new(key, SortOrder::default())),
+        };
+        self.consume()?;
+        Ok(SortKey::new(key, order))
+    }
+
+    /// # Grammar
+    /// `QUERY_ARGUMENTS_CONTENT -> QUERY_ARGUMENT_EXPRESSION , QUERY_ARGUMENTS_CONTENT | QUERY_ARGUMENT_EXPRESSION`
+    fn parse_query_arguments_content(&mut self) -> Result<Vec<QueryArgumentExpression>> {
         let mut arguments = Vec::new();
 
         loop {
-            let argument = self.parse_query_argument()?;
+            let argument = self.parse_query_argument_expression()?;
             arguments.push(argument);
 
             match self.peek()? {
@@ -316,45 +543,241 @@ impl<'src> Parser<'src> {
     }
 
     /// # Grammar
-    /// `QUERY_ARGUMENT -> QUERY_KEY QUERY_AGUMENT_OPERATION`
+    /// `QUERY_ARGUMENT_EXPRESSION -> QUERY_ARGUMENT_TERM OR QUERY_ARGUMENT_EXPRESSION | QUERY_ARGUMENT_TERM`
+    ///
+    /// `OR -> || | or`
+    fn parse_query_argument_expression(&mut self) -> Result<QueryArgumentExpression> {
+        let mut terms = vec![self.parse_query_argument_term()?];
+
+        while self.is_or()? {
+            self.consume()?;
+            terms.push(self.parse_query_argument_term()?);
+        }
+
+        match terms.len() {
+            1 => Ok(terms.remove(0)),
+            _ => Ok(QueryArgumentExpression::Or(terms)),
+        }
+    }
+
+    /// A key cannot follow a term, so `or` is always the keyword there
+    fn is_or(&mut self) -> Result<bool> {
+        match self.peek()? {
+            (Token::Or, _) => Ok(true),
+            (Token::Identifier(word), _) => Ok(word == "or"),
+            _ => Ok(false),
+        }
+    }
+
+    /// # Grammar
+    /// `QUERY_ARGUMENT_TERM -> NOT QUERY_ARGUMENT_TERM | QUERY_ARGUMENTS | QUERY_ARGUMENT`
+    ///
+    /// `NOT -> ! | not`
+    fn parse_query_argument_term(&mut self) -> Result<QueryArgumentExpression> {
+        if self.is_not()? {
+            self.consume()?;
+            let term = self.parse_query_argument_term()?;
+            return Ok(QueryArgumentExpression::Not(Box::new(term)));
+        }
+        match self.peek()? {
+            (Token::LParen, _) => self
+                .parse_query_arguments()
+                .map(QueryArgumentExpression::Group),
+            _ => self
+                .parse_query_argument()
+                .map(QueryArgumentExpression::Argument),
+        }
+    }
+
+    /// `not` is only a keyword when a term follows it, so fields named `not` can still be
+    /// used, as in `not = true`
+    fn is_not(&mut self) -> Result<bool> {
+        let is_not_word = match self.peek()? {
+            (Token::Not, _) => return Ok(true),
+            (Token::Identifier(word), _) => word == "not",
+            _ => false,
+        };
+        let is_before_term = matches!(self.peek_nth(1), Some(Token::LParen | Token::Not))
+            || self.is_keyword_before_key();
+        Ok(is_not_word && is_before_term)
+    }
+
+    /// # Grammar
+    /// `QUERY_ARGUMENT -> QUANTIFIER QUERY_ARGUMENT_KEY QUERY_ARGUMENT_RHS`
     fn parse_query_argument(&mut self) -> Result<QueryArgument> {
-        let key = self.parse_query_key()?;
-        let operation = self.parse_query_argument_operation()?;
-        Ok(QueryArgument::new(key, operation))
+        let quantifier = self.parse_quantifier()?;
+        let key = self.parse_query_argument_key()?;
+        let rhs = self.parse_query_argument_rhs()?;
+        Ok(QueryArgument::new(quantifier, key, rhs))
     }
 
     /// # Grammar
+    /// `QUERY_ARGUMENT_KEY -> @ | QUERY_KEY`
+    fn parse_query_argument_key(&mut self) -> Result<QueryKey> {
+        match self.peek()? {
+            // The element itself is inspected with an empty key
+            (Token::At, _) => {
+                self.consume()?;
+                Ok(QueryKey::default())
+            }
+            _ => self.parse_query_key(),
+        }
+    }
+
+    /// # Grammar
+    /// `QUANTIFIER -> any | all | none | ε`
+    ///
+    /// Quantifiers are only keywords when a key follows them, so fields named `any`, `all` or
+    /// `none` can still be used, as in `all = true`
+    fn parse_quantifier(&mut self) -> Result<Option<Quantifier>> {
+        let quantifier = match self.peek()? {
+            (Token::Identifier(word), _) => match word.as_str() {
+                "any" => Quantifier::Any,
+                "all" => Quantifier::All,
+                "none" => Quantifier::None,
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        if !self.is_keyword_before_key() {
+            return Ok(None);
+        }
+        self.consume()?;
+        Ok(Some(quantifier))
+    }
+
+    /// # Grammar
+    /// `QUERY_ARGUMENT_RHS -> QUERY_AGUMENT_OPERATION | COMPARISON_OPERATOR QUERY_KEY`
+    ///
     /// `QUERY_AGUMENT_OPERATION -> = QUERY_ARGUMENT_VALUE | != QUERY_ARGUMENT_VALUE
     ///     | > NUMBER | >= NUMBER
     ///     | < NUMBER | <= NUMBER
-    ///     | ~ REGEX | !~ REGEX`
-    fn parse_query_argument_operation(&mut self) -> Result<QueryArgumentOperation> {
-        match self.next_token()? {
-            (Token::Equal, _) => Ok(QueryArgumentOperation::Equal(
+    ///     | ~ REGEX_PATTERN | !~ REGEX_PATTERN
+    ///     | in QUERY_ARGUMENT_LIST | not in QUERY_ARGUMENT_LIST
+    ///     | = STRING_PATTERN | != STRING_PATTERN
+    ///     | ^= STRING_PATTERN | $= STRING_PATTERN | *= STRING_PATTERN`
+    ///
+    /// `COMPARISON_OPERATOR -> = | != | > | >= | < | <= | in | not in`
+    fn parse_query_argument_rhs(&mut self) -> Result<QueryArgumentRhs> {
+        let operator = match self.next_token()? {
+            (Token::Equal, _) => ComparisonOperator::Equal,
+            (Token::NotEqual, _) => ComparisonOperator::NotEqual,
+            (Token::Greater, _) => ComparisonOperator::Greater,
+            (Token::GreaterEqual, _) => ComparisonOperator::GreaterEqual,
+            (Token::Less, _) => ComparisonOperator::Less,
+            (Token::LessEqual, _) => ComparisonOperator::LessEqual,
+            (Token::Identifier(word), _) if word == "in" => ComparisonOperator::In,
+            (Token::Identifier(word), _) if word == "not" => match self.next_token()? {
+                (Token::Identifier(word), _) if word == "in" => ComparisonOperator::NotIn,
+                (unexpected_token, span) => {
+                    return Err(Error::UnexpectedToken(unexpected_token, span))
+                }
+            },
+            (Token::Tilde, _) => {
+                let pattern = self.parse_regex_pattern()?;
+                return Ok(QueryArgumentRhs::Operation(QueryArgumentOperation::Match(
+                    pattern,
+                )));
+            }
+            (Token::NotTilde, _) => {
+                let pattern = self.parse_regex_pattern()?;
+                return Ok(QueryArgumentRhs::Operation(
+                    QueryArgumentOperation::NotMatch(pattern),
+                ));
+            }
+            (Token::CaretEqual, _) => {
+                let pattern = self.parse_string_pattern()?;
+                return Ok(QueryArgumentRhs::Operation(
+                    QueryArgumentOperation::StartsWith(pattern),
+                ));
+            }
+            (Token::DollarEqual, _) => {
+                let pattern = self.parse_string_pattern()?;
+                return Ok(QueryArgumentRhs::Operation(
+                    QueryArgumentOperation::EndsWith(pattern),
+                ));
+            }
+            (Token::StarEqual, _) => {
+                let pattern = self.parse_string_pattern()?;
+                return Ok(QueryArgumentRhs::Operation(
+                    QueryArgumentOperation::Contains(pattern),
+                ));
+            }
+            (unexpected_token, span) => return Err(Error::UnexpectedToken(unexpected_token, span)),
+        };
+
+        // Literal strings are quoted, so an identifier can only start a key. Keys that have to be
+        // quoted cannot be compared, since they would be read as strings.
+        if let (Token::Identifier(_), _) = self.peek()? {
+            let key = self.parse_query_key()?;
+            return Ok(QueryArgumentRhs::KeyComparison(operator, key));
+        }
+
+        self.parse_query_argument_operation(operator)
+            .map(QueryArgumentRhs::Operation)
+    }
+
+    /// Parses the value of the operation of the given comparison operator, whose grammar is
+    /// described in [`Parser::parse_query_argument_rhs`]
+    fn parse_query_argument_operation(
+        &mut self,
+        operator: ComparisonOperator,
+    ) -> Result<QueryArgumentOperation> {
+        let is_equality = matches!(
+            operator,
+            ComparisonOperator::Equal | ComparisonOperator::NotEqual
+        );
+        if let (true, (Token::String(_), _)) = (is_equality, self.peek()?) {
+            let pattern = self.parse_string_pattern()?;
+            let equal = operator == ComparisonOperator::Equal;
+            return Ok(match (equal, *pattern.ignore_case()) {
+                (true, true) => QueryArgumentOperation::EqualIgnoreCase(pattern),
+                (false, true) => QueryArgumentOperation::NotEqualIgnoreCase(pattern),
+                (true, false) => QueryArgumentOperation::Equal(QueryArgumentValue::String(
+                    pattern.value().clone(),
+                )),
+                (false, false) => QueryArgumentOperation::NotEqual(QueryArgumentValue::String(
+                    pattern.value().clone(),
+                )),
+            });
+        }
+
+        match operator {
+            ComparisonOperator::Equal => Ok(QueryArgumentOperation::Equal(
                 self.parse_query_argument_value()?,
             )),
-            (Token::NotEqual, _) => Ok(QueryArgumentOperation::NotEqual(
+            ComparisonOperator::NotEqual => Ok(QueryArgumentOperation::NotEqual(
                 self.parse_query_argument_value()?,
             )),
-            (Token::Greater, _) => Ok(QueryArgumentOperation::Greater(self.parse_number()?)),
-            (Token::GreaterEqual, _) => {
+            ComparisonOperator::Greater => {
+                Ok(QueryArgumentOperation::Greater(self.parse_number()?))
+            }
+            ComparisonOperator::GreaterEqual => {
                 Ok(QueryArgumentOperation::GreaterEqual(self.parse_number()?))
             }
-            (Token::Less, _) => Ok(QueryArgumentOperation::Less(self.parse_number()?)),
-            (Token::LessEqual, _) => Ok(QueryArgumentOperation::LessEqual(self.parse_number()?)),
-            (Token::Tilde, _) => Ok(QueryArgumentOperation::Match(self.parse_regex()?)),
-            (Token::NotTilde, _) => Ok(QueryArgumentOperation::NotMatch(self.parse_regex()?)),
-            (unexpected_token, span) => Err(Error::UnexpectedToken(unexpected_token, span)),
+            ComparisonOperator::Less => Ok(QueryArgumentOperation::Less(self.parse_number()?)),
+            ComparisonOperator::LessEqual => {
+                Ok(QueryArgumentOperation::LessEqual(self.parse_number()?))
+            }
+            ComparisonOperator::In => Ok(QueryArgumentOperation::In(
+                self.parse_query_argument_list()?,
+            )),
+            ComparisonOperator::NotIn => Ok(QueryArgumentOperation::NotIn(
+                self.parse_query_argument_list()?,
+            )),
         }
     }
 
     /// # Grammar
-    /// `QUERY_ARGUMENT_VALUE -> string | NUMBER | boolean | null`
+    /// `QUERY_ARGUMENT_VALUE -> string | NUMBER | boolean | null | QUERY_ARGUMENT_LIST`
     fn parse_query_argument_value(&mut self) -> Result<QueryArgumentValue> {
         match self.peek()? {
             (Token::PosInteger(_), _) | (Token::NegInteger(_), _) | (Token::Float(_), _) => {
                 return Ok(QueryArgumentValue::Number(self.parse_number()?))
             }
+            (Token::LBracket, _) => {
+                return Ok(QueryArgumentValue::List(self.parse_query_argument_list()?))
+            }
             _ => (),
         }
         match self.next_token()? {
@@ -365,6 +788,34 @@ impl<'src> Parser<'src> {
             (unexpected_token, span) => Err(Error::UnexpectedToken(unexpected_token, span)),
         }
     }
+
+    /// # Grammar
+    /// `QUERY_ARGUMENT_LIST -> [ QUERY_ARGUMENT_LIST_CONTENT ] | [ ]`
+    ///
+    /// `QUERY_ARGUMENT_LIST_CONTENT -> QUERY_ARGUMENT_VALUE , QUERY_ARGUMENT_LIST_CONTENT | QUERY_ARGUMENT_VALUE`
+    fn parse_query_argument_list(&mut self) -> Result<Vec<QueryArgumentValue>> {
+        match self.next_token()? {
+            (Token::LBracket, _) => (),
+            (unexpected_token, span) => return Err(Error::UnexpectedToken(unexpected_token, span)),
+        };
+        if let (Token::RBracket, _) = self.peek()? {
+            self.consume()?;
+            return Ok(Vec::new());
+        }
+
+        let mut values = Vec::new();
+        loop {
+            values.push(self.parse_query_argument_value()?);
+            match self.next_token()? {
+                (Token::Comma, _) => (),
+                (Token::RBracket, _) => return Ok(values),
+                (unexpected_token, span) => {
+                    return Err(Error::UnexpectedToken(unexpected_token, span))
+                }
+            }
+        }
+    }
+
     /// # Grammar
     /// `NUMBER -> pos_integer | neg_integer | float`
     fn parse_number(&mut self) -> Result<Number> {
@@ -376,6 +827,60 @@ impl<'src> Parser<'src> {
         }
     }
 
+    /// # Grammar
+    /// `STRING_PATTERN -> string i | string`
+    ///
+    /// As regex flags, the `i` suffix must be attached to the string
+    fn parse_string_pattern(&mut self) -> Result<StringPattern> {
+        let (value, value_span) = match self.next_token()? {
+            (Token::String(value), span) => (value, span),
+            (unexpected_token, span) => return Err(Error::UnexpectedToken(unexpected_token, span)),
+        };
+        let ignore_case = match self.peek()? {
+            (Token::Identifier(suffix), span) if suffix == "i" && span.start == value_span.end => {
+                self.consume()?;
+                true
+            }
+            _ => false,
+        };
+        Ok(StringPattern::new(value, ignore_case))
+    }
+
+    /// # Grammar
+    /// `REGEX_PATTERN -> regex REGEX_FLAGS | regex`
+    ///
+    /// `REGEX_FLAGS -> identifier`
+    fn parse_regex_pattern(&mut self) -> Result<RegexPattern> {
+        let (value, regex_span) = match self.next_token()? {
+            (Token::String(value), span) => (value, span),
+            (unexpected_token, span) => return Err(Error::UnexpectedToken(unexpected_token, span)),
+        };
+        let mut builder = RegexBuilder::new(&value);
+
+        let flags = match self.peek()? {
+            // Flags must be attached to the regex, so they are not confused with keys or keywords
+            (Token::Identifier(flags), span) if span.start == regex_span.end => flags.clone(),
+            _ => String::new(),
+        };
+        if !flags.is_empty() {
+            let flags_span = self.consume()?;
+            for flag in flags.chars() {
+                match flag {
+                    'i' => builder.case_insensitive(true),
+                    'm' => builder.multi_line(true),
+                    's' => builder.dot_matches_new_line(true),
+                    'x' => builder.ignore_whitespace(true),
+                    flag => return Err(Error::RegexFlag(flag, flags_span)),
+                };
+            }
+        }
+
+        let regex = builder
+            .build()
+            .map_err(|err| Error::Regex(err, regex_span))?;
+        Ok(RegexPattern::new(regex, flags))
+    }
+
     /// # Grammar
     /// `REGEX -> regex`
     fn parse_regex(&mut self) -> Result<Regex> {
@@ -388,49 +893,95 @@ impl<'src> Parser<'src> {
     }
 
     /// # Grammar
-    /// `INDEX -> pos_integer | RANGE`
-    /// TODO: this parsing is very nasty, refactor it or think another way
+    /// `INDEX -> INTEGER | RANGE | RANGE STEP | : STEP`
+    ///
+    /// `RANGE -> INTEGER RANGE_END | RANGE_END`
     fn parse_index(&mut self) -> Result<IndexingValue> {
-        let first_number = match self.next_token()? {
-            // TODO correct way to handle usize casting
-            (Token::PosInteger(value), _) => value,
-            (unexpected_token, span) => return Err(Error::UnexpectedToken(unexpected_token, span)),
+        let range = match self.peek()? {
+            // `[::step]` is a shorthand for `[..:step]`
+            (Token::Colon, _) => {
+                self.consume()?;
+                let range = IndexingRange::new(None, Bound::Unbounded);
+                return Ok(IndexingValue::SteppedRange(range, self.parse_step()?));
+            }
+            (Token::DoubleDot, _) => self.parse_range_end(None)?,
+            _ => {
+                let index = self.parse_integer()?;
+                match self.peek()? {
+                    (Token::DoubleDot, _) => self.parse_range_end(Some(index))?,
+                    _ => return Ok(IndexingValue::Index(index)),
+                }
+            }
         };
 
         match self.peek()? {
-            (Token::Dot, _) => {
-                // TODO: handle u64 -> usize cast
-                let range = self.parse_range_end(first_number as usize)?;
-                Ok(IndexingValue::Range(range))
-            }
-            // TODO: handle u64 -> usize cast
-            _ => Ok(IndexingValue::Index(first_number as usize)),
+            (Token::Colon, _) => Ok(IndexingValue::SteppedRange(range, self.parse_step()?)),
+            _ => Ok(IndexingValue::Range(range)),
         }
     }
 
     /// # Grammar
-    /// `RANGE_END -> .. pos_integer`
-    // TODO: we should match rust range syntax, allowing for the `std::ops::RangeBound` trait
-    // https://doc.rust-lang.org/reference/expressions/range-expr.html
-    fn parse_range_end(&mut self, start: usize) -> Result<RangeInclusive<usize>> {
-        for _ in 0..2 {
-            match self.next_token()? {
-                (Token::Dot, _) => (),
-                (unexpected_token, span) => {
-                    return Err(Error::UnexpectedToken(unexpected_token, span))
-                }
-            }
+    /// `RANGE_END -> .. INTEGER | ..= INTEGER | ..< INTEGER | ..`
+    fn parse_range_end(&mut self, start: Option<isize>) -> Result<IndexingRange> {
+        match self.next_token()? {
+            (Token::DoubleDot, _) => (),
+            (unexpected_token, span) => return Err(Error::UnexpectedToken(unexpected_token, span)),
         }
 
-        let end = match self.next_token()? {
-            (Token::PosInteger(value), _) => value as usize,
-            (unexpected_token, span) => return Err(Error::UnexpectedToken(unexpected_token, span)),
+        let end = match self.peek()? {
+            (Token::Equal, _) => {
+                self.consume()?;
+                Bound::Included(self.parse_integer()?)
+            }
+            (Token::Less, _) => {
+                self.consume()?;
+                Bound::Excluded(self.parse_integer()?)
+            }
+            (Token::PosInteger(_), _) | (Token::NegInteger(_), _) => {
+                Bound::Included(self.parse_integer()?)
+            }
+            _ => Bound::Unbounded,
         };
 
-        Ok(start..=end)
+        Ok(IndexingRange::new(start, end))
+    }
+
+    /// # Grammar
+    /// `STEP -> : INTEGER`
+    fn parse_step(&mut self) -> Result<NonZeroIsize> {
+        match self.next_token()? {
+            (Token::Colon, _) => (),
+            (unexpected_token, span) => return Err(Error::UnexpectedToken(unexpected_token, span)),
+        }
+
+        let step_span = self.current_span()?;
+        let step = self.parse_integer()?;
+        NonZeroIsize::new(step).ok_or(Error::ZeroStep(step_span))
+    }
+
+    /// # Grammar
+    /// `INTEGER -> pos_integer | neg_integer`
+    fn parse_integer(&mut self) -> Result<isize> {
+        match self.next_token()? {
+            (Token::PosInteger(value), span) => {
+                isize::try_from(value).map_err(|err| Error::Integer(err, span))
+            }
+            (Token::NegInteger(value), span) => {
+                isize::try_from(value).map_err(|err| Error::Integer(err, span))
+            }
+            (unexpected_token, span) => Err(Error::UnexpectedToken(unexpected_token, span)),
+        }
     }
 }
 
+/// Whether the token can start the key of a query argument
+fn starts_argument_key(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::At | Token::Identifier(_) | Token::String(_) | Token::Star | Token::DoubleStar
+    )
+}
+
 impl FromStr for Query {
     type Err = Error;
 
@@ -442,8 +993,392 @@ impl FromStr for Query {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
     // TODO: add more parser tests
 
+    /// Asserts that the query is formatted as expected and that the formatted query
+    /// is parsed back into the same query
+    fn assert_format_round_trips(input: &str, expected: &str) {
+        let formatted = input.parse::<Query>().unwrap().to_string();
+        assert_eq!(formatted, expected);
+
+        let reformatted = formatted.parse::<Query>().unwrap().to_string();
+        assert_eq!(reformatted, formatted);
+    }
+
+    #[rstest]
+    #[case::or("items(a = 1 || b = 2)", "items(a=1 || b=2)")]
+    #[case::or_keyword("items(a = 1 or b = 2)", "items(a=1 || b=2)")]
+    #[case::not("items(!a = 1)", "items(!a=1)")]
+    #[case::not_keyword("items(not a = 1)", "items(!a=1)")]
+    #[case::double_not("items(!!a = 1)", "items(!!a=1)")]
+    #[case::group("items((a = 1, b = 2) || c = 3)", "items((a=1, b=2) || c=3)")]
+    #[case::not_group("items(!(a = 1 || b = 2), c = 3)", "items(!(a=1 || b=2), c=3)")]
+    #[case::or_inside_and("items(a = 1, b = 2 || c = 3)", "items(a=1, b=2 || c=3)")]
+    fn argument_expression_format_round_trips(#[case] input: &str, #[case] expected: &str) {
+        assert_format_round_trips(input, expected);
+    }
+
+    #[rstest]
+    #[case::string(r#"a(x = "a\"b\\c")"#, r#"a(x="a\"b\\c")"#)]
+    #[case::string_pattern(r#"a(x ^= "a\"b\\c")"#, r#"a(x^="a\"b\\c")"#)]
+    #[case::ignore_case_pattern(r#"a(x $= "a\"b"i)"#, r#"a(x$="a\"b"i)"#)]
+    #[case::list(r#"a(x in ["a\"b", "c\\d"])"#, r#"a(x in ["a\"b", "c\\d"])"#)]
+    #[case::default("{\n  x ?? \"a\\\"b\"\n}", "{\n  x ?? \"a\\\"b\"\n}")]
+    fn escaped_string_format_round_trips(#[case] input: &str, #[case] expected: &str) {
+        assert_format_round_trips(input, expected);
+    }
+
+    #[rstest]
+    #[case::wildcard_equal("items(tags.*=\"x\")", "items(tags.*=\"x\")")]
+    #[case::spaced_wildcard_equal("items(tags.* = \"x\")", "items(tags.*=\"x\")")]
+    #[case::wildcard_equal_first("items(*=1)", "items(*=1)")]
+    #[case::contains("items(tags*=\"x\")", "items(tags*=\"x\")")]
+    fn wildcard_argument_key_format_round_trips(#[case] input: &str, #[case] expected: &str) {
+        assert_format_round_trips(input, expected);
+    }
+
+    #[rstest]
+    #[case::index("items[1]", "items[1]")]
+    #[case::negative_index("items[-1]", "items[-1]")]
+    #[case::range("items[1..2]", "items[1..2]")]
+    #[case::inclusive_range("items[1..=2]", "items[1..2]")]
+    #[case::exclusive_range("items[0..<-1]", "items[0..<-1]")]
+    #[case::open_end_range("items[-3..]", "items[-3..]")]
+    #[case::open_start_range("items[..2]", "items[..2]")]
+    #[case::full_range("items[..]", "items[..]")]
+    #[case::stepped_range("items[0..100:10]", "items[0..100:10]")]
+    #[case::negative_stepped_range("items[..:-1]", "items[..:-1]")]
+    #[case::stepped_range_shorthand("items[::-1]", "items[..:-1]")]
+    fn indexing_format_round_trips(#[case] input: &str, #[case] expected: &str) {
+        assert_format_round_trips(input, expected);
+    }
+
+    #[test]
+    fn index_bigger_than_isize_max_fails() {
+        let result = "items[18446744073709551615]".parse::<Query>();
+        assert!(matches!(result, Err(Error::Integer(_, _))));
+    }
+
+    #[rstest]
+    #[case::values("items[sort]", "items[sort]")]
+    #[case::single_key("items[sort(price)]", "items[sort(price)]")]
+    #[case::ascending("items[sort(price asc)]", "items[sort(price)]")]
+    #[case::multiple_keys(
+        "items[sort(price desc, name, a.b desc)]",
+        "items[sort(price desc, name, a.b desc)]"
+    )]
+    #[case::key_named_as_order("items[sort(desc desc)]", "items[sort(desc desc)]")]
+    #[case::chained_with_indexing("items[sort(price desc)][0..9]", "items[sort(price desc)][0..9]")]
+    fn sort_format_round_trips(#[case] input: &str, #[case] expected: &str) {
+        assert_format_round_trips(input, expected);
+    }
+
+    #[rstest]
+    #[case::count("items[count]")]
+    #[case::sum("items.price[sum]")]
+    #[case::avg("items(a>1).price[avg]")]
+    #[case::min("items.price[min]")]
+    #[case::max("items.price[max]")]
+    #[case::after_sort("items[sort(price)][0..2].price[sum]")]
+    fn aggregation_format_round_trips(#[case] input: &str) {
+        assert_format_round_trips(input, input);
+    }
+
+    #[rstest]
+    #[case::simple("events[groupBy(type)]")]
+    #[case::nested_key("events[groupBy(service.name)]")]
+    #[case::with_children("events[groupBy(type)] {\n  message\n}")]
+    fn group_by_format_round_trips(#[case] input: &str) {
+        assert_format_round_trips(input, input);
+    }
+
+    #[rstest]
+    #[case::unique("regions[unique]")]
+    #[case::unique_by("stores[uniqueBy(address.region)]")]
+    #[case::chained("stores.region[unique][sort]")]
+    fn unique_format_round_trips(#[case] input: &str) {
+        assert_format_round_trips(input, input);
+    }
+
+    #[rstest]
+    #[case::default_depth("teams.members[flatten]", "teams.members[flatten]")]
+    #[case::explicit_default_depth("teams.members[flatten(1)]", "teams.members[flatten]")]
+    #[case::depth("teams.members[flatten(3)]", "teams.members[flatten(3)]")]
+    fn flatten_format_round_trips(#[case] input: &str, #[case] expected: &str) {
+        assert_format_round_trips(input, expected);
+    }
+
+    #[test]
+    fn negative_flatten_depth_fails() {
+        let result = "teams[flatten(-1)]".parse::<Query>();
+        assert!(matches!(
+            result,
+            Err(Error::UnexpectedToken(Token::NegInteger(-1), _))
+        ));
+    }
+
+    #[test]
+    fn group_by_without_key_fails() {
+        let result = "events[groupBy]".parse::<Query>();
+        assert!(matches!(
+            result,
+            Err(Error::UnexpectedToken(Token::RBracket, _))
+        ));
+    }
+
+    #[rstest]
+    #[case::wildcard("users.*")]
+    #[case::root_wildcard("*.name")]
+    #[case::nested_wildcards("users.*.addresses.*.city")]
+    #[case::wildcard_with_arguments("users.*(age>30).name")]
+    #[case::wildcard_with_children("users.* {\n  name\n}")]
+    fn wildcard_format_round_trips(#[case] input: &str) {
+        assert_format_round_trips(input, input);
+    }
+
+    #[rstest]
+    #[case::descendants("**.id")]
+    #[case::nested_descendants("spec.**.containers.**.image")]
+    #[case::descendants_with_arguments("**.containers(name=\"app\").image")]
+    #[case::descendants_with_children("{\n  **.metadata {\n    name\n  }\n}")]
+    fn descendants_format_round_trips(#[case] input: &str) {
+        assert_format_round_trips(input, input);
+    }
+
+    #[rstest]
+    #[case::descendants_with_children("spec.** { a }")]
+    #[case::descendants_with_arguments("**(a=1).id")]
+    fn descendants_must_be_followed_by_key(#[case] input: &str) {
+        let result = input.parse::<Query>();
+        assert!(matches!(result, Err(Error::UnexpectedToken(_, _))));
+    }
+
+    #[test]
+    fn trailing_descendants_fails() {
+        let result = "spec.**".parse::<Query>();
+        assert!(matches!(result, Err(Error::UnexpectedEndOfInput(_))));
+    }
+
+    #[rstest]
+    #[case::pattern("paths.*.~\"^x-\"")]
+    #[case::root_pattern("~\"^(get|post)$\".summary")]
+    #[case::escaped_pattern("~\"^\\\\d+\\\"$\"")]
+    #[case::pattern_with_arguments("~\"^u\"(age>30).name")]
+    fn pattern_format_round_trips(#[case] input: &str) {
+        assert_format_round_trips(input, input);
+    }
+
+    #[test]
+    fn invalid_pattern_fails() {
+        let result = "~\"(\"".parse::<Query>();
+        assert!(matches!(result, Err(Error::Regex(_, _))));
+    }
+
+    #[rstest]
+    #[case::optional("user.email?")]
+    #[case::optional_in_the_middle("user.address?.city")]
+    #[case::optional_with_arguments_and_operators("items?(price>1)[0]")]
+    #[case::optional_with_children("{\n  address? {\n    city?\n  }\n}")]
+    fn optional_format_round_trips(#[case] input: &str) {
+        assert_format_round_trips(input, input);
+    }
+
+    #[rstest]
+    #[case::string_default("{\n  currency ?? \"EUR\"\n}")]
+    #[case::number_default("{\n  retries ?? 3\n}")]
+    #[case::float_default("{\n  rate ?? 1.005\n}")]
+    #[case::round_float_default("{\n  rate ?? 2.0\n}")]
+    #[case::small_float_default("{\n  rate ?? 1.5e-7\n}")]
+    #[case::big_float_default("{\n  rate ?? 1.0e300\n}")]
+    #[case::bool_default("{\n  active ?? false\n}")]
+    #[case::default_with_alias("{\n  cost: price ?? 0\n}")]
+    #[case::default_with_children("{\n  address ?? null {\n    city\n  }\n}")]
+    fn default_format_round_trips(#[case] input: &str) {
+        assert_format_round_trips(input, input);
+    }
+
+    #[test]
+    fn wildcard_without_alias_fails() {
+        let result = "{ * }".parse::<Query>();
+        assert!(matches!(result, Err(Error::Construction(_, _))));
+    }
+
+    #[test]
+    fn wildcard_alias_fails() {
+        let result = "{ users: * }".parse::<Query>();
+        assert!(matches!(
+            result,
+            Err(Error::UnexpectedToken(Token::Star, _))
+        ));
+    }
+
+    #[test]
+    fn unknown_operator_fails() {
+        let result = "items[shuffle]".parse::<Query>();
+        assert!(matches!(result, Err(Error::UnknownOperator(name, _)) if name == "shuffle"));
+    }
+
+    #[test]
+    fn zero_step_fails() {
+        let result = "items[..:0]".parse::<Query>();
+        assert!(matches!(result, Err(Error::ZeroStep(_))));
+    }
+
+    #[rstest]
+    #[case::or_key("items(or = 1)", "items(or=1)")]
+    #[case::not_key("items(not = 1 or not != 2)", "items(not=1 || not!=2)")]
+    #[case::negated_not_key("items(not not ~ \"x\")", "items(!not~\"x\")")]
+    #[case::quoted_key(r#"items("or" = 1)"#, r#"items("or"=1)"#)]
+    #[case::or_after_regex(r#"items(a ~ "x" or b = 1)"#, r#"items(a~"x" || b=1)"#)]
+    fn or_and_not_keys(#[case] input: &str, #[case] expected: &str) {
+        assert_format_round_trips(input, expected);
+    }
+
+    #[rstest]
+    #[case::root("not")]
+    #[case::children("{\n  or\n  not\n}")]
+    #[case::nested("stats.or.not")]
+    fn or_and_not_fields(#[case] input: &str) {
+        assert_format_round_trips(input, input);
+    }
+
+    #[test]
+    fn keyword_keys_must_be_quoted() {
+        assert_format_round_trips(r#"items("null" = 1)"#, r#"items("null"=1)"#);
+    }
+
+    #[rstest]
+    #[case::in_key("items(in in [1])", "items(in in [1])")]
+    #[case::not_key_in("items(not in [1])", "items(not in [1])")]
+    #[case::not_key_not_in("items(not not in [1])", "items(not not in [1])")]
+    #[case::quantifier_key_not_in("items(all not in [1])", "items(all not in [1])")]
+    #[case::negated_in_key("items(!in = 1)", "items(!in=1)")]
+    #[case::compared_in_key("items(a = in)", "items(a=in)")]
+    fn in_keys(#[case] input: &str, #[case] expected: &str) {
+        assert_format_round_trips(input, expected);
+    }
+
+    #[rstest]
+    #[case::root("in")]
+    #[case::nested("stats.in")]
+    fn in_fields(#[case] input: &str) {
+        assert_format_round_trips(input, input);
+    }
+
+    #[rstest]
+    #[case::self_reference("tags(@ ~ \"^prod\")", "tags(@~\"^prod\")")]
+    #[case::self_reference_with_quantifier("matrix(all @ > 1)", "matrix(all @>1)")]
+    #[case::self_reference_and_key("items(@ != null, price > 1)", "items(@!=null, price>1)")]
+    fn self_reference_format_round_trips(#[case] input: &str, #[case] expected: &str) {
+        assert_format_round_trips(input, expected);
+    }
+
+    #[rstest]
+    #[case::any("items(any tags = \"x\")", "items(any tags=\"x\")")]
+    #[case::all("items(all scores > 5)", "items(all scores>5)")]
+    #[case::none("items(none tags ~ \"^x\")", "items(none tags~\"^x\")")]
+    #[case::negated("items(!all scores > 5)", "items(!all scores>5)")]
+    #[case::or("items(all a = 1 || none b = 2)", "items(all a=1 || none b=2)")]
+    fn quantifier_format_round_trips(#[case] input: &str, #[case] expected: &str) {
+        assert_format_round_trips(input, expected);
+    }
+
+    #[rstest]
+    #[case::all_key("items(all = true)", "items(all=true)")]
+    #[case::quantified_all_key("items(all all = 1)", "items(all all=1)")]
+    #[case::none_key_with_path("items(none.count > 1)", "items(none.count>1)")]
+    #[case::quoted_key(r#"items(any "any" = 1)"#, r#"items(any "any"=1)"#)]
+    fn quantifier_keys(#[case] input: &str, #[case] expected: &str) {
+        assert_format_round_trips(input, expected);
+    }
+
+    #[rstest]
+    #[case::root("any")]
+    #[case::children("{\n  all\n  none\n}")]
+    #[case::nested("stats.any.all")]
+    fn quantifier_fields(#[case] input: &str) {
+        assert_format_round_trips(input, input);
+    }
+
+    #[rstest]
+    #[case::greater("items(shipped_at > ordered_at)", "items(shipped_at>ordered_at)")]
+    #[case::nested_key("accounts(used >= limits.quota)", "accounts(used>=limits.quota)")]
+    #[case::equal("items(a = b, c != d)", "items(a=b, c!=d)")]
+    #[case::string_value("items(a = \"b\")", "items(a=\"b\")")]
+    #[case::with_quantifier("items(all scores < max)", "items(all scores<max)")]
+    fn key_comparison_format_round_trips(#[case] input: &str, #[case] expected: &str) {
+        assert_format_round_trips(input, expected);
+    }
+
+    #[rstest]
+    #[case::in_list(
+        r#"items(status in ["open","blocked"])"#,
+        r#"items(status in ["open", "blocked"])"#
+    )]
+    #[case::not_in("items(id not in [1, 2, 3])", "items(id not in [1, 2, 3])")]
+    #[case::empty("items(id in [])", "items(id in [])")]
+    #[case::nested(
+        r#"items(pair in [[1, 2], ["a"]])"#,
+        r#"items(pair in [[1, 2], ["a"]])"#
+    )]
+    #[case::list_equality("items(tags = [1, 2])", "items(tags=[1, 2])")]
+    #[case::key("items(status in allowed)", "items(status in allowed)")]
+    #[case::with_quantifier("items(all tags not in [1])", "items(all tags not in [1])")]
+    fn in_format_round_trips(#[case] input: &str, #[case] expected: &str) {
+        assert_format_round_trips(input, expected);
+    }
+
+    #[rstest]
+    #[case::starts_with(r#"hosts(name ^= "api.")"#, r#"hosts(name^="api.")"#)]
+    #[case::ends_with(r#"hosts(name $= ".example.com")"#, r#"hosts(name$=".example.com")"#)]
+    #[case::contains(r#"hosts(name *= "prod")"#, r#"hosts(name*="prod")"#)]
+    #[case::ignore_case(r#"hosts(name ^= "API"i)"#, r#"hosts(name^="API"i)"#)]
+    #[case::equal_ignore_case(r#"users(name = "alice"i)"#, r#"users(name="alice"i)"#)]
+    #[case::not_equal_ignore_case(r#"users(name != "alice"i)"#, r#"users(name!="alice"i)"#)]
+    #[case::equal(r#"users(name = "alice")"#, r#"users(name="alice")"#)]
+    fn string_operation_format_round_trips(#[case] input: &str, #[case] expected: &str) {
+        assert_format_round_trips(input, expected);
+    }
+
+    #[rstest]
+    #[case::not_string("hosts(name ^= 1)")]
+    #[case::key("hosts(name ^= prefix)")]
+    #[case::unknown_suffix(r#"hosts(name ^= "a"x)"#)]
+    fn invalid_string_operation(#[case] input: &str) {
+        assert!(input.parse::<Query>().is_err());
+    }
+
+    #[rstest]
+    #[case::ignore_case(r#"logs(message ~ "error"i)"#, r#"logs(message~"error"i)"#)]
+    #[case::multiple_flags(r#"logs(message !~ "^error$"ims)"#, r#"logs(message!~"^error$"ims)"#)]
+    #[case::no_flags(r#"logs(message ~ "error")"#, r#"logs(message~"error")"#)]
+    #[case::escaped(r#"logs(message ~ "\"\\d+\""i)"#, r#"logs(message~"\"\\d+\""i)"#)]
+    fn regex_flags_format_round_trips(#[case] input: &str, #[case] expected: &str) {
+        assert_format_round_trips(input, expected);
+    }
+
+    #[rstest]
+    #[case::regex_flags(r#"logs(message ~ "error" i)"#)]
+    #[case::string_suffix(r#"hosts(name $= ".COM" i)"#)]
+    fn detached_suffix_fails(#[case] input: &str) {
+        assert!(input.parse::<Query>().is_err());
+    }
+
+    #[test]
+    fn unknown_regex_flag_fails() {
+        let result = r#"logs(message ~ "error"ig)"#.parse::<Query>();
+        assert!(matches!(result, Err(Error::RegexFlag('g', _))));
+    }
+
+    #[rstest]
+    #[case::missing_list("items(id in 1)")]
+    #[case::unclosed_list("items(id in [1, 2)")]
+    #[case::not_without_in("items(id not [1])")]
+    fn invalid_in(#[case] input: &str) {
+        assert!(input.parse::<Query>().is_err());
+    }
+
     #[test]
     #[should_panic]
     fn same_alias() {
@@ -2,12 +2,14 @@ use std::collections::HashSet;
 
 use derive_builder::{Builder, UninitializedFieldError};
 use derive_getters::Getters;
-use query_key::{QueryKey, RawKey};
+use query_arguments::QueryArgumentValue;
+use query_key::{AtomicQueryKey, QueryKey, RawKey};
 use query_operators::QueryOperators;
 use thiserror::Error;
 
 pub mod apply;
 mod context;
+pub mod diagnostic;
 pub mod format;
 pub mod query_arguments;
 pub mod query_key;
@@ -66,7 +68,7 @@ impl QueryBuilder {
         };
         for child in children {
             let child_query_key = child.output_key();
-            if !output_keys.insert(child_query_key) {
+            if !output_keys.insert(child_query_key.as_str()) {
                 return Err(RootQueryValidationError::DuplicatedOutputKeyInRoot(
                     child_query_key.clone(),
                 ));
@@ -81,6 +83,8 @@ pub enum ChildQueryValidationError {
     // TODO: maybe we shouldnt wrap RawKey between ' '?
     #[error("query '{0}' has children with duplicated output keys: '{1}'")]
     DuplicatedOutputKey(String, RawKey),
+    #[error("query '{0}' only selects multiple keys, so it needs an alias to name its output")]
+    MissingAlias(String),
 }
 
 #[derive(Debug, Error)]
@@ -99,6 +103,9 @@ pub enum ChildQueryBuilderError {
 pub struct ChildQuery {
     #[builder(default)]
     alias: Option<RawKey>,
+    /// Value output instead of a missing or `null` one
+    #[builder(default)]
+    default_value: Option<QueryArgumentValue>,
     // TODO: those fields should not be pub, they must be validated
     pub key: QueryKey,
     #[builder(default)]
@@ -107,8 +114,20 @@ pub struct ChildQuery {
 
 impl ChildQueryBuilder {
     fn validate(&self) -> Result<(), ChildQueryValidationError> {
+        self.validate_alias()?;
         self.validate_children()
     }
+    /// Keys that select multiple keys keep the keys of the object they are matched against, so
+    /// the output needs to be named after a previous key (`users.*`) or an alias
+    fn validate_alias(&self) -> Result<(), ChildQueryValidationError> {
+        let (None | Some(None), Some(key)) = (&self.alias, &self.key) else {
+            return Ok(());
+        };
+        if key.keys().iter().all(|key| key.key().is_multiple()) {
+            return Err(ChildQueryValidationError::MissingAlias(key.to_string()));
+        }
+        Ok(())
+    }
     fn validate_children(&self) -> Result<(), ChildQueryValidationError> {
         let mut output_keys = HashSet::new();
         let Some(children) = self.children.as_ref() else {
@@ -116,7 +135,7 @@ impl ChildQueryBuilder {
         };
         for child in children {
             let child_output_key = child.output_key();
-            if !output_keys.insert(child_output_key) {
+            if !output_keys.insert(child_output_key.as_str()) {
                 let child_key = self.key.as_ref().expect("child key must be defined");
                 return Err(ChildQueryValidationError::DuplicatedOutputKey(
                     child_key.to_string(),
@@ -129,9 +148,16 @@ impl ChildQueryBuilder {
 }
 
 impl ChildQuery {
+    /// The alias, if any, or the last key that does not select multiple keys, since those keep the
+    /// keys of the object they are matched against (`users.*` outputs `users`)
     pub fn output_key(&self) -> &RawKey {
-        self.alias()
-            .as_ref()
-            .unwrap_or_else(|| self.key().last_key().key())
+        self.alias().as_ref().unwrap_or_else(|| {
+            self.key()
+                .keys()
+                .iter()
+                .map(AtomicQueryKey::key)
+                .rfind(|key| !key.is_multiple())
+                .expect("queries that only select multiple keys must have an alias")
+        })
     }
 }
@@ -1,12 +1,46 @@
+use std::borrow::Cow;
+
 use serde_json::Value;
 use thiserror::Error;
 
 use super::{
     context::{Context, JsonPath, OwnedJsonPath},
+    diagnostic::Diagnostic,
+    query_operators::OutOfBounds,
     ChildQuery, Query,
 };
 
-#[derive(Debug, Error)]
+/// Behaviour for the elements of an array that are empty objects or arrays once the query is
+/// applied to them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EmptyElements {
+    /// Remove them from the array
+    #[default]
+    Remove,
+    /// Keep them as they are
+    Keep,
+    /// Replace them with `null`, as well as the elements discarded by an error, so the array keeps
+    /// its length
+    Null,
+}
+
+/// Options that tweak how a query is applied to a value
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApplyOptions {
+    pub out_of_bounds: OutOfBounds,
+    pub empty_elements: EmptyElements,
+    /// Fail on the first error inside an array instead of discarding the element that caused it
+    pub strict: bool,
+}
+
+/// Output of a query along with the diagnostics found while applying it
+#[derive(Debug, Clone)]
+pub struct ApplyOutput {
+    pub value: Value,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Error, Clone)]
 pub enum Error {
     // TODO: use a JsonPath<'static> instead of OwnedJsonPath?
     #[error("key '{0}' not found")]
@@ -15,8 +49,12 @@ pub enum Error {
     InsideArray(Box<Self>, OwnedJsonPath),
     #[error("tried to index a non-indexable value (neither object nor array) at '{0}'")]
     NonIndexableValue(OwnedJsonPath),
-    #[error("tried to apply arguments in a non-filtrable value (not an array) at '{0}'")]
+    #[error(
+        "tried to apply arguments in a non-filtrable value (neither array nor object) at '{0}'"
+    )]
     NonFiltrableValue(OwnedJsonPath),
+    #[error("object at '{0}' does not satisfy the arguments")]
+    UnsatisfiedArguments(OwnedJsonPath),
     #[error("{0}")]
     QueryOperatorError(#[from] super::query_operators::Error),
 }
@@ -35,6 +73,9 @@ impl From<InternalError<'_>> for Error {
             InternalError::NonFiltrableValue(path) => {
                 Error::NonFiltrableValue(OwnedJsonPath::from(&path))
             }
+            InternalError::UnsatisfiedArguments(path) => {
+                Error::UnsatisfiedArguments(OwnedJsonPath::from(&path))
+            }
             InternalError::QueryOperatorError(error) => Error::QueryOperatorError(error),
         }
     }
@@ -49,15 +90,40 @@ pub enum InternalError<'a> {
     InsideArray(Box<Self>, JsonPath<'a>),
     #[error("tried to index a non-indexable value (neither object nor array) at '{0}'")]
     NonIndexableValue(JsonPath<'a>),
-    #[error("tried to apply arguments in a non-filtrable value (not an array) at '{0}'")]
+    #[error(
+        "tried to apply arguments in a non-filtrable value (neither array nor object) at '{0}'"
+    )]
     NonFiltrableValue(JsonPath<'a>),
+    /// An object inside an array mapping does not satisfy its arguments, so the mapped element
+    /// is filtered out, as with the arguments of arrays
+    #[error("object at '{0}' does not satisfy the arguments")]
+    UnsatisfiedArguments(JsonPath<'a>),
     #[error("{0}")]
     QueryOperatorError(#[from] super::query_operators::Error),
 }
 
 impl Query {
     pub fn apply(&self, root_json: Value) -> Result<Value, Error> {
-        let root_context = Context::new();
+        self.apply_with_options(root_json, ApplyOptions::default())
+    }
+
+    pub fn apply_with_options(
+        &self,
+        root_json: Value,
+        options: ApplyOptions,
+    ) -> Result<Value, Error> {
+        self.apply_with_diagnostics(root_json, options)
+            .map(|output| output.value)
+    }
+
+    /// Applies the query, collecting the soft failures found along the way, such as the elements
+    /// of arrays that were discarded due to an error
+    pub fn apply_with_diagnostics(
+        &self,
+        root_json: Value,
+        options: ApplyOptions,
+    ) -> Result<ApplyOutput, Error> {
+        let root_context = Context::new(options);
 
         let root_query_key = self.key();
         let new_root_json = root_query_key.inspect_owned_with_arguments_and_operator(
@@ -69,12 +135,74 @@ impl Query {
         // TODO: maybe the inspect function should return the inspected context
         let new_context = root_context.push_query_key(root_query_key);
 
-        Ok(self.do_apply(new_root_json, new_context)?)
+        let value = self.do_apply_inspected(new_root_json, new_context)?;
+        Ok(ApplyOutput {
+            value,
+            diagnostics: root_context.take_diagnostics(),
+        })
     }
 }
 
 trait QueryApply {
     fn children(&self) -> &Vec<ChildQuery>;
+    /// Number of nested objects whose values are selected by the query key, such as the
+    /// objects matched by a wildcard or the objects of groups
+    fn keyed_levels(&self) -> usize;
+    /// Whether the inspected value may be a `null` that was not in the input
+    fn is_nullable(&self) -> bool;
+
+    /// Applies the query to the value inspected by its key. If the key outputs keyed objects,
+    /// their values are projected instead of the objects themselves.
+    fn do_apply_inspected<'a>(
+        &'a self,
+        value: Value,
+        context: Context<'a>,
+    ) -> Result<Value, InternalError<'a>> {
+        self.do_apply_keyed(value, context, self.keyed_levels())
+    }
+
+    fn do_apply_keyed<'a>(
+        &'a self,
+        value: Value,
+        context: Context<'a>,
+        levels: usize,
+    ) -> Result<Value, InternalError<'a>> {
+        if levels == 0 {
+            return self.do_apply(value, context);
+        }
+
+        match value {
+            // As in arrays, an error only discards the value that caused it
+            Value::Object(object) => {
+                let object_context = context.enter_array();
+                let projected_object = object
+                    .into_iter()
+                    .map(|(key, item)| {
+                        let item_context = object_context.push_object_key(key.clone());
+                        self.do_apply_keyed(item, item_context, levels - 1)
+                            .map(|value| (key, value))
+                    })
+                    .filter_map(|result| object_context.array_item_result(result))
+                    .collect::<Result<_, _>>()?;
+                Ok(Value::Object(projected_object))
+            }
+            // The key was accessed through an array, so each item is a keyed object
+            Value::Array(array) => {
+                let array_context = context.enter_array();
+                let projected_array = array
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, item)| {
+                        self.do_apply_keyed(item, array_context.push_index(index), levels)
+                    })
+                    .filter_map(|result| array_context.array_element_result(result))
+                    .collect::<Result<_, _>>()?;
+                Ok(Value::Array(projected_array))
+            }
+            value => self.do_apply(value, context),
+        }
+    }
+
     fn do_apply<'a>(
         &'a self,
         value: Value,
@@ -82,7 +210,7 @@ trait QueryApply {
     ) -> Result<Value, InternalError<'a>> {
         match value {
             Value::Object(_) => self.do_apply_object(value, context),
-            Value::Array(array) => Ok(self.do_apply_array(array, context)),
+            Value::Array(array) => self.do_apply_array(array, context),
             _ => self.do_apply_primitive(value, context),
         }
     }
@@ -92,7 +220,10 @@ trait QueryApply {
         value: Value,
         context: Context<'a>,
     ) -> Result<Value, InternalError> {
-        if !self.children().is_empty() {
+        // Optional keys output `null` when they are missing, as do objects that do not satisfy
+        // their arguments, which have no children to select
+        let is_missing = value.is_null() && self.is_nullable();
+        if !self.children().is_empty() && !is_missing {
             return Err(InternalError::NonIndexableValue(context.path().clone()));
         }
         Ok(value)
@@ -113,30 +244,28 @@ trait QueryApply {
             let child_value_result = child_query_key.inspect(&value, &context);
             let child_context = context.push_query_key(child_query_key);
 
+            if let Some(default) = child.default_for(&child_value_result) {
+                filtered_object.insert(child.output_key().as_str().to_string(), default);
+                continue;
+            }
+
             let child_value = match (child_value_result, child_context.array_context()) {
                 (Ok(value), _) => value.into_owned(),
                 (Err(internal_error), None) => return Err(internal_error),
                 (Err(internal_error), Some(array_context)) => {
-                    let array_error = InternalError::InsideArray(
-                        Box::new(internal_error),
-                        array_context.path().clone(),
-                    );
-                    log::warn!("{array_error}");
+                    child_context.discard_array_error(internal_error, array_context.path())?;
                     continue;
                 }
             };
 
-            let child_filtered_value_result = child.do_apply(child_value, child_context.clone());
+            let child_filtered_value_result =
+                child.do_apply_inspected(child_value, child_context.clone());
             let child_filtered_value =
                 match (child_filtered_value_result, child_context.array_context()) {
                     (Ok(value), _) => value,
                     (Err(child_error), None) => return Err(child_error),
                     (Err(child_error), Some(array_context)) => {
-                        let array_error = InternalError::InsideArray(
-                            Box::new(child_error),
-                            array_context.path().clone(),
-                        );
-                        log::warn!("{array_error}");
+                        child_context.discard_array_error(child_error, array_context.path())?;
                         continue;
                     }
                 };
@@ -147,31 +276,36 @@ trait QueryApply {
         }
         Ok(Value::Object(filtered_object))
     }
-    fn do_apply_array(&self, array: Vec<Value>, context: Context) -> Value {
+    fn do_apply_array<'a>(
+        &'a self,
+        array: Vec<Value>,
+        context: Context<'a>,
+    ) -> Result<Value, InternalError<'a>> {
         let array_context = context.enter_array();
         let filtered_array = array
             .into_iter()
             .enumerate()
             .map(|(index, item)| (array_context.push_index(index), item))
             .map(|(item_context, item)| self.do_apply(item, item_context))
-            .flat_map(|result| {
-                result
-                    .map_err(|error| {
-                        let array_error = InternalError::InsideArray(
-                            Box::new(error),
-                            array_context.path().clone(),
-                        );
-                        log::warn!("{array_error}");
-                    })
-                    .ok()
+            .filter_map(|result| array_context.array_element_result(result))
+            .filter_map(|result| match result {
+                Ok(value) if is_empty(&value) => match context.options().empty_elements {
+                    EmptyElements::Remove => None,
+                    EmptyElements::Keep => Some(Ok(value)),
+                    EmptyElements::Null => Some(Ok(Value::Null)),
+                },
+                result => Some(result),
             })
-            .filter(|value| match value {
-                Value::Object(object) => !object.is_empty(),
-                Value::Array(array) => !array.is_empty(),
-                _ => true,
-            })
-            .collect();
-        Value::Array(filtered_array)
+            .collect::<Result<_, _>>()?;
+        Ok(Value::Array(filtered_array))
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Object(object) => object.is_empty(),
+        Value::Array(array) => array.is_empty(),
+        _ => false,
     }
 }
 
@@ -179,10 +313,41 @@ impl QueryApply for Query {
     fn children(&self) -> &Vec<ChildQuery> {
         self.children()
     }
+
+    fn keyed_levels(&self) -> usize {
+        match self.key().keys().last() {
+            Some(_) => self.key().keyed_levels(),
+            None => usize::from(self.operators().outputs_groups()),
+        }
+    }
+
+    fn is_nullable(&self) -> bool {
+        self.key().is_nullable() || !self.arguments().0.is_empty()
+    }
+}
+
+impl ChildQuery {
+    /// The default value, if any, when the inspected value is missing or `null`
+    fn default_for(&self, inspected: &Result<Cow<Value>, InternalError>) -> Option<Value> {
+        let default = self.default_value().as_ref()?;
+        match inspected {
+            Ok(value) if value.is_null() => Some(Value::from(default)),
+            Err(InternalError::KeyNotFound(_)) => Some(Value::from(default)),
+            _ => None,
+        }
+    }
 }
 
 impl QueryApply for ChildQuery {
     fn children(&self) -> &Vec<ChildQuery> {
         self.children()
     }
+
+    fn keyed_levels(&self) -> usize {
+        self.key().keyed_levels()
+    }
+
+    fn is_nullable(&self) -> bool {
+        self.key().is_nullable()
+    }
 }
@@ -1,16 +1,25 @@
 use std::{
+    borrow::Cow,
+    cell::RefCell,
     fmt::{self, Display, Formatter},
     rc::Rc,
 };
 
 use derive_getters::Getters;
+use logos::Span;
+use serde_json::Value;
 
-use super::query_key::{QueryKey, RawKey};
+use super::{
+    apply::{ApplyOptions, EmptyElements, InternalError},
+    diagnostic::{Diagnostic, DiagnosticKind},
+    query_key::{AtomicQueryKey, QueryKey, RawKey},
+};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum JsonPathEntry<'a> {
     // TODO: should we use &str or RawKey?
-    Key(&'a str),
+    // Keys are owned when they come from the input, such as the keys matched by a wildcard
+    Key(Cow<'a, str>),
     Index(usize),
 }
 
@@ -108,19 +117,40 @@ pub struct ArrayContext<'a> {
 pub struct Context<'a> {
     path: JsonPath<'a>,
     array_context: Option<ArrayContext<'a>>,
+    options: ApplyOptions,
+    /// Span of the query key being applied
+    span: Option<Span>,
+    // Shared by every context derived from the root one, so diagnostics are collected once
+    #[getter(skip)]
+    diagnostics: Rc<RefCell<Vec<Diagnostic>>>,
 }
 
 impl<'a> Context<'a> {
-    pub fn new() -> Self {
-        Self::default()
+    pub fn new(options: ApplyOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
     }
 
     // TODO: see if &'a is necessary
     pub fn push_raw_key(&self, raw_key: &'a RawKey) -> Context<'a> {
-        let entry = JsonPathEntry::Key(raw_key.as_str());
+        let entry = JsonPathEntry::Key(Cow::Borrowed(raw_key.as_str()));
+        self.push_entry(entry)
+    }
+
+    /// Pushes a key of the input value, which is not part of the query
+    pub fn push_object_key(&self, key: String) -> Context<'a> {
+        let entry = JsonPathEntry::Key(Cow::Owned(key));
         self.push_entry(entry)
     }
 
+    /// Pushes the raw key of an atomic key, which becomes the key being applied
+    pub fn push_atomic_key(&self, atomic_query_key: &'a AtomicQueryKey) -> Context<'a> {
+        self.push_raw_key(atomic_query_key.key())
+            .with_span(Some(atomic_query_key.span().clone()))
+    }
+
     pub fn push_index(&self, index: usize) -> Context<'a> {
         let entry = JsonPathEntry::Index(index);
         self.push_entry(entry)
@@ -131,10 +161,20 @@ impl<'a> Context<'a> {
         let mut path = self.path.clone();
         // TODO: cleanup here
         for atomic_query_key in query_key.keys() {
-            path = path.push(JsonPathEntry::Key(atomic_query_key.key().as_str()));
+            path = path.push(JsonPathEntry::Key(Cow::Borrowed(
+                atomic_query_key.key().as_str(),
+            )));
         }
         Self {
             path,
+            span: query_key.span().or_else(|| self.span.clone()),
+            ..self.clone()
+        }
+    }
+
+    pub fn with_span(&self, span: Option<Span>) -> Self {
+        Self {
+            span,
             ..self.clone()
         }
     }
@@ -148,6 +188,62 @@ impl<'a> Context<'a> {
         }
     }
 
+    /// Handles an error raised by an element of the array at `array_path`. The error is logged
+    /// and only the element is discarded, unless the evaluation is strict.
+    pub fn discard_array_error(
+        &self,
+        error: InternalError<'a>,
+        array_path: &JsonPath<'a>,
+    ) -> Result<(), InternalError<'a>> {
+        let array_error = InternalError::InsideArray(Box::new(error), array_path.clone());
+        if self.options.strict {
+            return Err(array_error);
+        }
+        self.report(DiagnosticKind::DiscardedElement, array_path, &array_error);
+        Ok(())
+    }
+
+    /// Result of an element of the array entered at this context, which is `None` if its error
+    /// was discarded or if it was filtered out by the arguments of a mapped object
+    pub fn array_item_result<T>(
+        &self,
+        result: Result<T, InternalError<'a>>,
+    ) -> Option<Result<T, InternalError<'a>>> {
+        match result {
+            Ok(value) => Some(Ok(value)),
+            Err(InternalError::UnsatisfiedArguments(_)) => None,
+            Err(error) => self.discard_array_error(error, &self.path).err().map(Err),
+        }
+    }
+
+    /// Result of an element of the array entered at this context. If its error was discarded, the
+    /// element is removed, or replaced with `null` when empty elements are, so the array keeps its
+    /// length. Elements filtered out by arguments are always removed.
+    pub fn array_element_result(
+        &self,
+        result: Result<Value, InternalError<'a>>,
+    ) -> Option<Result<Value, InternalError<'a>>> {
+        let is_filtered = matches!(result, Err(InternalError::UnsatisfiedArguments(_)));
+        match (self.array_item_result(result), self.options.empty_elements) {
+            (None, EmptyElements::Null) if !is_filtered => Some(Ok(Value::Null)),
+            (result, _) => result,
+        }
+    }
+
+    /// Logs a soft failure found at the given path and collects it as a diagnostic
+    pub fn report(&self, kind: DiagnosticKind, path: &JsonPath, message: &dyn Display) {
+        let message = message.to_string();
+        log::log!(kind.level(), "{message}");
+        let diagnostic =
+            Diagnostic::new(kind, OwnedJsonPath::from(path), message, self.span.clone());
+        self.diagnostics.borrow_mut().push(diagnostic);
+    }
+
+    /// Takes the diagnostics collected by this context and the ones derived from it
+    pub fn take_diagnostics(&self) -> Vec<Diagnostic> {
+        self.diagnostics.take()
+    }
+
     fn push_entry(&self, entry: JsonPathEntry<'a>) -> Context<'a> {
         Self {
             path: self.path.push(entry),
@@ -161,6 +257,9 @@ impl Default for Context<'_> {
         Self {
             path: JsonPath::Root,
             array_context: None,
+            options: ApplyOptions::default(),
+            span: None,
+            diagnostics: Default::default(),
         }
     }
 }
@@ -0,0 +1,63 @@
+use std::fmt::{self, Display, Formatter};
+
+use derive_getters::Getters;
+use derive_more::Constructor;
+use logos::Span;
+
+use super::context::OwnedJsonPath;
+
+/// Soft failures that do not stop the query from being applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DiagnosticKind {
+    /// An element of an array, or a value selected by a wildcard, was discarded due to an error
+    DiscardedElement,
+    /// An argument could not be evaluated, so the value did not satisfy it
+    ArgumentError,
+    /// A key was not found and `null` was used instead
+    NullDefault,
+    /// Different values were grouped together because they have the same group name, such as
+    /// `1` and `"1"`
+    MergedGroup,
+}
+
+impl DiagnosticKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::DiscardedElement => "discardedElement",
+            Self::ArgumentError => "argumentError",
+            Self::NullDefault => "nullDefault",
+            Self::MergedGroup => "mergedGroup",
+        }
+    }
+
+    /// Level at which diagnostics of this kind are logged. Keys defaulting to `null` are
+    /// usually expected, so they are not warnings.
+    pub fn level(&self) -> log::Level {
+        match self {
+            Self::DiscardedElement | Self::ArgumentError | Self::MergedGroup => log::Level::Warn,
+            Self::NullDefault => log::Level::Info,
+        }
+    }
+}
+
+impl Display for DiagnosticKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+#[derive(Debug, Clone, Constructor, Getters)]
+pub struct Diagnostic {
+    kind: DiagnosticKind,
+    /// Path of the input value where the diagnostic was found
+    path: OwnedJsonPath,
+    message: String,
+    /// Position in the query source of the key being applied, if known
+    span: Option<Span>,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
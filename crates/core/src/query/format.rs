@@ -77,6 +77,9 @@ impl ChildQuery {
         if let Some(alias) = self.alias() {
             result.push_str(&format!(": {alias}"));
         }
+        if let Some(default) = self.default_value() {
+            result.push_str(&format!(" ?? {default}"));
+        }
 
         if !self.children().is_empty() {
             result.push_str(&format!(" {{{sep}"));
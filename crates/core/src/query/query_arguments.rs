@@ -1,6 +1,7 @@
 use super::{
     apply::InternalError,
     context::{Context, JsonPath},
+    diagnostic::DiagnosticKind,
     QueryKey,
 };
 use derive_getters::Getters;
@@ -9,6 +10,7 @@ use regex::Regex;
 use serde_json::Value;
 use std::{
     borrow::Cow,
+    cmp::Ordering,
     fmt::{self, Display, Formatter},
 };
 use thiserror::Error;
@@ -72,6 +74,7 @@ impl ValueType for QueryArgumentValue {
             QueryArgumentValue::Number(_) => "number".to_string(),
             QueryArgumentValue::Bool(_) => "bool".to_string(),
             QueryArgumentValue::Null => "null".to_string(),
+            QueryArgumentValue::List(_) => "array".to_string(),
         }
     }
 }
@@ -91,6 +94,18 @@ impl ValueType for Regex {
     }
 }
 
+impl ValueType for RegexPattern {
+    fn value_type(&self) -> String {
+        "regex".to_string()
+    }
+}
+
+impl ValueType for StringPattern {
+    fn value_type(&self) -> String {
+        "string".to_string()
+    }
+}
+
 impl ValueType for QueryArgumentOperation {
     fn value_type(&self) -> String {
         match self {
@@ -102,6 +117,12 @@ impl ValueType for QueryArgumentOperation {
             QueryArgumentOperation::LessEqual(value) => value.value_type(),
             QueryArgumentOperation::Match(value) => value.value_type(),
             QueryArgumentOperation::NotMatch(value) => value.value_type(),
+            QueryArgumentOperation::EqualIgnoreCase(value) => value.value_type(),
+            QueryArgumentOperation::NotEqualIgnoreCase(value) => value.value_type(),
+            QueryArgumentOperation::StartsWith(value) => value.value_type(),
+            QueryArgumentOperation::EndsWith(value) => value.value_type(),
+            QueryArgumentOperation::Contains(value) => value.value_type(),
+            QueryArgumentOperation::In(_) | QueryArgumentOperation::NotIn(_) => "array".to_string(),
         }
     }
 }
@@ -121,6 +142,13 @@ impl OperationType for QueryArgumentOperation {
             QueryArgumentOperation::LessEqual(_) => "<=".to_string(),
             QueryArgumentOperation::Match(_) => "~".to_string(),
             QueryArgumentOperation::NotMatch(_) => "!~".to_string(),
+            QueryArgumentOperation::EqualIgnoreCase(_) => "=".to_string(),
+            QueryArgumentOperation::NotEqualIgnoreCase(_) => "!=".to_string(),
+            QueryArgumentOperation::StartsWith(_) => "^=".to_string(),
+            QueryArgumentOperation::EndsWith(_) => "$=".to_string(),
+            QueryArgumentOperation::Contains(_) => "*=".to_string(),
+            QueryArgumentOperation::In(_) => "in".to_string(),
+            QueryArgumentOperation::NotIn(_) => "not in".to_string(),
         }
     }
 }
@@ -137,7 +165,16 @@ impl Display for Number {
         match self {
             Self::PosInteger(value) => value.fmt(f),
             Self::NegInteger(value) => value.fmt(f),
-            Self::Float(value) => write!(f, "{value:.2}"),
+            Self::Float(value) => {
+                // The shortest representation that parses back into the same value, which always
+                // needs a fractional part to be lexed as a float (`1.0e300` instead of `1e300`)
+                let value = format!("{value:?}");
+                match value.split_once('e') {
+                    _ if value.contains('.') => f.write_str(&value),
+                    Some((mantissa, exponent)) => write!(f, "{mantissa}.0e{exponent}"),
+                    None => write!(f, "{value}.0"),
+                }
+            }
         }
     }
 }
@@ -160,23 +197,95 @@ impl From<f64> for Number {
     }
 }
 
-impl PartialEq<serde_json::Number> for Number {
-    fn eq(&self, other: &serde_json::Number) -> bool {
+impl From<&serde_json::Number> for Number {
+    fn from(number: &serde_json::Number) -> Self {
+        match (number.as_u64(), number.as_i64()) {
+            (Some(value), _) => Self::PosInteger(value),
+            (None, Some(value)) => Self::NegInteger(value),
+            (None, None) => Self::Float(number.as_f64().expect("wrong conversion")),
+        }
+    }
+}
+
+impl From<&Number> for Value {
+    fn from(number: &Number) -> Self {
+        match number {
+            Number::PosInteger(value) => Value::from(*value),
+            Number::NegInteger(value) => Value::from(*value),
+            Number::Float(value) => Value::from(*value),
+        }
+    }
+}
+
+impl Number {
+    /// Integers are widened to `i128`, which can represent every `u64` and `i64` value
+    fn as_integer(&self) -> Option<i128> {
         match self {
-            Self::NegInteger(lhs_i64) if other.is_i64() => {
-                other.as_i64().expect("wrong conversion").eq(lhs_i64)
-            }
-            Self::PosInteger(lhs_u64) if other.is_u64() => {
-                other.as_u64().expect("wrong conversion").eq(lhs_u64)
-            }
-            Self::Float(lhs_f64) if other.is_f64() => {
-                other.as_f64().expect("wrong conversion").eq(lhs_f64)
-            }
-            _ => todo!("handle number implicit casting"),
+            Self::PosInteger(value) => Some(i128::from(*value)),
+            Self::NegInteger(value) => Some(i128::from(*value)),
+            Self::Float(_) => None,
+        }
+    }
+
+    fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Float(value) => Some(*value),
+            Self::PosInteger(_) | Self::NegInteger(_) => None,
         }
     }
 }
 
+/// Compares an integer with a float without rounding any of them, since not every `u64` and
+/// `i64` value can be represented as `f64` and not every `f64` value is an integer
+fn cmp_integer_float(integer: i128, float: f64) -> Option<Ordering> {
+    // Floats out of the range of `u64` and `i64` are greater or less than any of their values
+    const INTEGER_UPPER_BOUND: f64 = 18_446_744_073_709_551_616.0;
+    const INTEGER_LOWER_BOUND: f64 = -9_223_372_036_854_775_808.0;
+
+    if float.is_nan() {
+        return None;
+    }
+    if float >= INTEGER_UPPER_BOUND {
+        return Some(Ordering::Less);
+    }
+    if float < INTEGER_LOWER_BOUND {
+        return Some(Ordering::Greater);
+    }
+
+    // Inside that range, the integer part of the float is exact, and its fractional part
+    // breaks the tie with integers equal to it
+    let truncated = float.trunc();
+    let fraction = float - truncated;
+    let ordering = integer.cmp(&(truncated as i128)).then_with(|| {
+        0.0.partial_cmp(&fraction)
+            .expect("the fractional part of a finite float is never NaN")
+    });
+    Some(ordering)
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self.as_integer(), other.as_integer()) {
+            (Some(lhs), Some(rhs)) => Some(lhs.cmp(&rhs)),
+            (Some(lhs), None) => cmp_integer_float(lhs, other.as_float()?),
+            (None, Some(rhs)) => cmp_integer_float(rhs, self.as_float()?).map(Ordering::reverse),
+            (None, None) => self.as_float()?.partial_cmp(&other.as_float()?),
+        }
+    }
+}
+
+impl PartialEq<serde_json::Number> for Number {
+    fn eq(&self, other: &serde_json::Number) -> bool {
+        self.eq(&Number::from(other))
+    }
+}
+
 impl PartialEq<Number> for serde_json::Number {
     fn eq(&self, other: &Number) -> bool {
         other.eq(self)
@@ -184,28 +293,14 @@ impl PartialEq<Number> for serde_json::Number {
 }
 
 impl PartialOrd<serde_json::Number> for Number {
-    fn partial_cmp(&self, other: &serde_json::Number) -> Option<std::cmp::Ordering> {
-        match self {
-            Self::NegInteger(lhs_i64) if other.is_i64() => other
-                .as_i64()
-                .expect("wrong conversion")
-                .partial_cmp(lhs_i64),
-            Self::PosInteger(lhs_u64) if other.is_u64() => other
-                .as_u64()
-                .expect("wrong conversion")
-                .partial_cmp(lhs_u64),
-            Self::Float(lhs_f64) if other.is_f64() => other
-                .as_f64()
-                .expect("wrong conversion")
-                .partial_cmp(lhs_f64),
-            _ => todo!("handle number implicit casting"),
-        }
+    fn partial_cmp(&self, other: &serde_json::Number) -> Option<Ordering> {
+        self.partial_cmp(&Number::from(other))
     }
 }
 
 impl PartialOrd<Number> for serde_json::Number {
-    fn partial_cmp(&self, other: &Number) -> Option<std::cmp::Ordering> {
-        other.partial_cmp(self)
+    fn partial_cmp(&self, other: &Number) -> Option<Ordering> {
+        other.partial_cmp(self).map(Ordering::reverse)
     }
 }
 
@@ -215,15 +310,207 @@ pub enum QueryArgumentValue {
     Number(Number),
     Bool(bool),
     Null,
+    List(Vec<QueryArgumentValue>),
+}
+
+impl QueryArgumentValue {
+    /// Converts a JSON value into an argument value. Objects have no literal representation.
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::String(value) => Some(QueryArgumentValue::String(value.clone())),
+            Value::Number(value) => Some(QueryArgumentValue::Number(value.into())),
+            Value::Bool(value) => Some(QueryArgumentValue::Bool(*value)),
+            Value::Null => Some(QueryArgumentValue::Null),
+            Value::Array(array) => array
+                .iter()
+                .map(Self::from_value)
+                .collect::<Option<_>>()
+                .map(QueryArgumentValue::List),
+            Value::Object(_) => None,
+        }
+    }
+
+    fn fmt_list(values: &[QueryArgumentValue], f: &mut Formatter) -> fmt::Result {
+        let values = values
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "[{values}]")
+    }
 }
 
 impl Display for QueryArgumentValue {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
-            QueryArgumentValue::String(value) => write!(f, "\"{value}\""),
+            QueryArgumentValue::String(value) => {
+                let escaped_value = escape8259::escape(value);
+                write!(f, "\"{escaped_value}\"")
+            }
             QueryArgumentValue::Number(value) => write!(f, "{value}"),
             QueryArgumentValue::Bool(value) => write!(f, "{value}"),
             QueryArgumentValue::Null => write!(f, "null"),
+            QueryArgumentValue::List(values) => Self::fmt_list(values, f),
+        }
+    }
+}
+
+/// Values of different types are not equal, and lists are only equal to arrays with the same
+/// elements in the same order
+impl PartialEq<Value> for QueryArgumentValue {
+    fn eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (QueryArgumentValue::String(value), Value::String(other)) => value == other,
+            (QueryArgumentValue::Number(value), Value::Number(other)) => value == other,
+            (QueryArgumentValue::Bool(value), Value::Bool(other)) => value == other,
+            (QueryArgumentValue::Null, Value::Null) => true,
+            (QueryArgumentValue::List(values), Value::Array(array)) => {
+                values.len() == array.len()
+                    && values.iter().zip(array).all(|(value, item)| value == item)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<&QueryArgumentValue> for Value {
+    fn from(value: &QueryArgumentValue) -> Self {
+        match value {
+            QueryArgumentValue::String(value) => Value::String(value.clone()),
+            QueryArgumentValue::Number(value) => Value::from(value),
+            QueryArgumentValue::Bool(value) => Value::Bool(*value),
+            QueryArgumentValue::Null => Value::Null,
+            QueryArgumentValue::List(values) => {
+                Value::Array(values.iter().map(Value::from).collect())
+            }
+        }
+    }
+}
+
+/// String value of the string operations, which can be compared ignoring case with the `i` suffix.
+/// Both strings are lowercased to ignore the case, so letters whose uppercase form has several
+/// letters do not match it (`"STRASSE"i` does not match `"straße"`).
+#[derive(Debug, Clone, Constructor, Getters)]
+pub struct StringPattern {
+    value: String,
+    ignore_case: bool,
+}
+
+impl Display for StringPattern {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let escaped_value = escape8259::escape(self.value());
+        match self.ignore_case {
+            true => write!(f, "\"{escaped_value}\"i"),
+            false => write!(f, "\"{escaped_value}\""),
+        }
+    }
+}
+
+impl StringPattern {
+    fn satisfies(&self, value: &str, predicate: fn(&str, &str) -> bool) -> bool {
+        match self.ignore_case {
+            true => predicate(&value.to_lowercase(), &self.value.to_lowercase()),
+            false => predicate(value, &self.value),
+        }
+    }
+}
+
+/// Regex of the match operations, which keeps the flags it was built with to display them
+#[derive(Debug, Clone, Constructor, Getters)]
+pub struct RegexPattern {
+    regex: Regex,
+    flags: String,
+}
+
+impl Display for RegexPattern {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let escaped_regex = escape8259::escape(self.regex().as_str());
+        let flags = self.flags();
+        write!(f, "\"{escaped_regex}\"{flags}")
+    }
+}
+
+/// How many elements of an array value must satisfy an operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantifier {
+    Any,
+    All,
+    None,
+}
+
+impl Display for Quantifier {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Quantifier::Any => "any".fmt(f),
+            Quantifier::All => "all".fmt(f),
+            Quantifier::None => "none".fmt(f),
+        }
+    }
+}
+
+impl Quantifier {
+    fn satisfies(&self, mut results: impl Iterator<Item = bool>) -> bool {
+        match self {
+            Quantifier::Any => results.any(|result| result),
+            Quantifier::All => results.all(|result| result),
+            Quantifier::None => !results.any(|result| result),
+        }
+    }
+}
+
+/// Operators that can compare the key of an argument with another key of the same element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparisonOperator {
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+    In,
+    NotIn,
+}
+
+impl Display for ComparisonOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ComparisonOperator::Equal => "=".fmt(f),
+            ComparisonOperator::NotEqual => "!=".fmt(f),
+            ComparisonOperator::Greater => ">".fmt(f),
+            ComparisonOperator::GreaterEqual => ">=".fmt(f),
+            ComparisonOperator::Less => "<".fmt(f),
+            ComparisonOperator::LessEqual => "<=".fmt(f),
+            ComparisonOperator::In => "in".fmt(f),
+            ComparisonOperator::NotIn => "not in".fmt(f),
+        }
+    }
+}
+
+impl ComparisonOperator {
+    /// Builds the operation that compares with the given value, if the operator supports its type
+    fn operation_with(&self, value: &Value) -> Option<QueryArgumentOperation> {
+        match (self, QueryArgumentValue::from_value(value)?) {
+            (ComparisonOperator::Equal, value) => Some(QueryArgumentOperation::Equal(value)),
+            (ComparisonOperator::NotEqual, value) => Some(QueryArgumentOperation::NotEqual(value)),
+            (ComparisonOperator::Greater, QueryArgumentValue::Number(value)) => {
+                Some(QueryArgumentOperation::Greater(value))
+            }
+            (ComparisonOperator::GreaterEqual, QueryArgumentValue::Number(value)) => {
+                Some(QueryArgumentOperation::GreaterEqual(value))
+            }
+            (ComparisonOperator::Less, QueryArgumentValue::Number(value)) => {
+                Some(QueryArgumentOperation::Less(value))
+            }
+            (ComparisonOperator::LessEqual, QueryArgumentValue::Number(value)) => {
+                Some(QueryArgumentOperation::LessEqual(value))
+            }
+            (ComparisonOperator::In, QueryArgumentValue::List(values)) => {
+                Some(QueryArgumentOperation::In(values))
+            }
+            (ComparisonOperator::NotIn, QueryArgumentValue::List(values)) => {
+                Some(QueryArgumentOperation::NotIn(values))
+            }
+            _ => None,
         }
     }
 }
@@ -236,8 +523,16 @@ pub enum QueryArgumentOperation {
     GreaterEqual(Number),
     Less(Number),
     LessEqual(Number),
-    Match(Regex),
-    NotMatch(Regex),
+    Match(RegexPattern),
+    NotMatch(RegexPattern),
+    In(Vec<QueryArgumentValue>),
+    NotIn(Vec<QueryArgumentValue>),
+    // Only built with the `i` suffix, since case-sensitive string equality is `Equal`
+    EqualIgnoreCase(StringPattern),
+    NotEqualIgnoreCase(StringPattern),
+    StartsWith(StringPattern),
+    EndsWith(StringPattern),
+    Contains(StringPattern),
 }
 
 impl Display for QueryArgumentOperation {
@@ -250,8 +545,43 @@ impl Display for QueryArgumentOperation {
             QueryArgumentOperation::GreaterEqual(value) => write!(f, ">={value}"),
             QueryArgumentOperation::Less(value) => write!(f, "<{value}"),
             QueryArgumentOperation::LessEqual(value) => write!(f, "<={value}"),
-            QueryArgumentOperation::Match(regex) => write!(f, "~\"{regex}\""),
-            QueryArgumentOperation::NotMatch(regex) => write!(f, "!~\"{regex}\""),
+            QueryArgumentOperation::Match(pattern) => write!(f, "~{pattern}"),
+            QueryArgumentOperation::NotMatch(pattern) => write!(f, "!~{pattern}"),
+            QueryArgumentOperation::EqualIgnoreCase(pattern) => write!(f, "={pattern}"),
+            QueryArgumentOperation::NotEqualIgnoreCase(pattern) => write!(f, "!={pattern}"),
+            QueryArgumentOperation::StartsWith(pattern) => write!(f, "^={pattern}"),
+            QueryArgumentOperation::EndsWith(pattern) => write!(f, "$={pattern}"),
+            QueryArgumentOperation::Contains(pattern) => write!(f, "*={pattern}"),
+            QueryArgumentOperation::In(values) => {
+                " in ".fmt(f)?;
+                QueryArgumentValue::fmt_list(values, f)
+            }
+            QueryArgumentOperation::NotIn(values) => {
+                " not in ".fmt(f)?;
+                QueryArgumentValue::fmt_list(values, f)
+            }
+        }
+    }
+}
+
+/// Right-hand side of an argument, which is either an operation with literal values or a
+/// comparison with another key of the same element
+#[derive(Debug, Clone)]
+pub enum QueryArgumentRhs {
+    Operation(QueryArgumentOperation),
+    KeyComparison(ComparisonOperator, QueryKey),
+}
+
+impl Display for QueryArgumentRhs {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            QueryArgumentRhs::Operation(operation) => operation.fmt(f),
+            // Keyword operators have to be separated from the keys
+            QueryArgumentRhs::KeyComparison(
+                operator @ (ComparisonOperator::In | ComparisonOperator::NotIn),
+                key,
+            ) => write!(f, " {operator} {key}"),
+            QueryArgumentRhs::KeyComparison(operator, key) => write!(f, "{operator}{key}"),
         }
     }
 }
@@ -283,25 +613,62 @@ impl<'a> QueryArgumentOperation {
             QueryArgumentOperation::NotMatch(operation_value) => self
                 .satisfies_match(operation_value, value, context)
                 .map(|result| !result),
+            QueryArgumentOperation::EqualIgnoreCase(operation_value) => {
+                self.satisfies_string(operation_value, str::eq, value, context)
+            }
+            QueryArgumentOperation::NotEqualIgnoreCase(operation_value) => self
+                .satisfies_string(operation_value, str::eq, value, context)
+                .map(|result| !result),
+            QueryArgumentOperation::StartsWith(operation_value) => self.satisfies_string(
+                operation_value,
+                |value, pattern| value.starts_with(pattern),
+                value,
+                context,
+            ),
+            QueryArgumentOperation::EndsWith(operation_value) => self.satisfies_string(
+                operation_value,
+                |value, pattern| value.ends_with(pattern),
+                value,
+                context,
+            ),
+            QueryArgumentOperation::Contains(operation_value) => self.satisfies_string(
+                operation_value,
+                |value, pattern| value.contains(pattern),
+                value,
+                context,
+            ),
+            QueryArgumentOperation::In(operation_values) => {
+                Self::satisfies_in(operation_values, value, context)
+            }
+            QueryArgumentOperation::NotIn(operation_values) => {
+                Self::satisfies_in(operation_values, value, context).map(|result| !result)
+            }
         }
     }
 
-    fn satisfies_op_array<F>(array: &[Value], satisfies_op: F, context: &Context<'a>) -> bool
+    /// Elements whose operation fails are reported and do not satisfy it
+    fn satisfies_op_array<F>(
+        array: &[Value],
+        satisfies_op: F,
+        quantifier: Quantifier,
+        context: &Context<'a>,
+    ) -> bool
     where
         F: Fn(&Value, &Context<'a>) -> Result<bool, Error<'a>>,
     {
-        array
+        let results = array
             .iter()
             .enumerate()
             .map(|(index, item)| (context.push_index(index), item))
             .map(|(item_context, item)| satisfies_op(item, &item_context))
-            .any(|result| {
+            .map(|result| {
                 result
                     .map_err(|error| {
-                        log::warn!("{error}");
+                        context.report(DiagnosticKind::ArgumentError, context.path(), &error);
                     })
                     .unwrap_or(false)
-            })
+            });
+        quantifier.satisfies(results)
     }
 
     fn satisfies_equal(
@@ -323,15 +690,52 @@ impl<'a> QueryArgumentOperation {
             (QueryArgumentValue::Null, Value::Null) => Ok(true),
             (QueryArgumentValue::Null, _) => Ok(false),
             (_, Value::Null) => Ok(false),
+            // Lists are compared with the whole array instead of with each of its elements
+            (QueryArgumentValue::List(_), Value::Array(_)) => Ok(operation_value == value),
             (_, Value::Array(array)) => {
                 let satisfies_op = |item: &Value, context: &Context<'a>| {
                     self.satisfies_equal(operation_value, item, context)
                 };
-                Ok(Self::satisfies_op_array(array, satisfies_op, context))
+                Ok(Self::satisfies_op_array(
+                    array,
+                    satisfies_op,
+                    Quantifier::Any,
+                    context,
+                ))
             }
             _ => Err(self.incomparable_types_error(operation_value, value, context)),
         }
     }
+
+    /// Values are compared as with `=`, but values whose type is different from the type of an
+    /// element of the list are not in it instead of being incomparable
+    fn satisfies_in(
+        operation_values: &[QueryArgumentValue],
+        value: &Value,
+        context: &Context<'a>,
+    ) -> Result<bool, Error<'a>> {
+        if operation_values
+            .iter()
+            .any(|operation_value| operation_value == value)
+        {
+            return Ok(true);
+        }
+        match value {
+            Value::Array(array) => {
+                let satisfies_op = |item: &Value, context: &Context<'a>| {
+                    Self::satisfies_in(operation_values, item, context)
+                };
+                Ok(Self::satisfies_op_array(
+                    array,
+                    satisfies_op,
+                    Quantifier::Any,
+                    context,
+                ))
+            }
+            _ => Ok(false),
+        }
+    }
+
     fn satisfies_greater(
         &self,
         operation_value: &Number,
@@ -344,7 +748,12 @@ impl<'a> QueryArgumentOperation {
                 let satisfies_op = |item: &Value, context: &Context<'a>| {
                     self.satisfies_greater(operation_value, item, context)
                 };
-                Ok(Self::satisfies_op_array(array, satisfies_op, context))
+                Ok(Self::satisfies_op_array(
+                    array,
+                    satisfies_op,
+                    Quantifier::Any,
+                    context,
+                ))
             }
             _ => Err(self.incompatible_operation_error(value, context)),
         }
@@ -362,7 +771,12 @@ impl<'a> QueryArgumentOperation {
                 let satisfies_op = |item: &Value, context: &Context<'a>| {
                     self.satisfies_less(operation_value, item, context)
                 };
-                Ok(Self::satisfies_op_array(array, satisfies_op, context))
+                Ok(Self::satisfies_op_array(
+                    array,
+                    satisfies_op,
+                    Quantifier::Any,
+                    context,
+                ))
             }
             _ => Err(self.incompatible_operation_error(value, context)),
         }
@@ -370,17 +784,46 @@ impl<'a> QueryArgumentOperation {
 
     fn satisfies_match(
         &self,
-        operation_value: &Regex,
+        operation_value: &RegexPattern,
         value: &Value,
         context: &Context<'a>,
     ) -> Result<bool, Error<'a>> {
         match value {
-            Value::String(value) => Ok(operation_value.is_match(value)),
+            Value::String(value) => Ok(operation_value.regex().is_match(value)),
             Value::Array(array) => {
                 let satisfies_op = |item: &Value, context: &Context<'a>| {
                     self.satisfies_match(operation_value, item, context)
                 };
-                Ok(Self::satisfies_op_array(array, satisfies_op, context))
+                Ok(Self::satisfies_op_array(
+                    array,
+                    satisfies_op,
+                    Quantifier::Any,
+                    context,
+                ))
+            }
+            _ => Err(self.incompatible_operation_error(value, context)),
+        }
+    }
+
+    fn satisfies_string(
+        &self,
+        operation_value: &StringPattern,
+        predicate: fn(&str, &str) -> bool,
+        value: &Value,
+        context: &Context<'a>,
+    ) -> Result<bool, Error<'a>> {
+        match value {
+            Value::String(value) => Ok(operation_value.satisfies(value, predicate)),
+            Value::Array(array) => {
+                let satisfies_op = |item: &Value, context: &Context<'a>| {
+                    self.satisfies_string(operation_value, predicate, item, context)
+                };
+                Ok(Self::satisfies_op_array(
+                    array,
+                    satisfies_op,
+                    Quantifier::Any,
+                    context,
+                ))
             }
             _ => Err(self.incompatible_operation_error(value, context)),
         }
@@ -417,20 +860,83 @@ impl<'a> QueryArgumentOperation {
 
 #[derive(Debug, Clone, Constructor, Getters)]
 pub struct QueryArgument {
+    /// Applies the operation to each element of array values. Without it, an array value
+    /// satisfies the operation if any of its elements does.
+    quantifier: Option<Quantifier>,
     key: QueryKey,
-    operation: QueryArgumentOperation,
+    rhs: QueryArgumentRhs,
 }
 
 impl Display for QueryArgument {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        let key = self.key();
-        let operation = self.operation();
-        write!(f, "{key}{operation}")
+        // An empty key references the element itself
+        let key = match self.key().keys().is_empty() {
+            true => "@".to_string(),
+            false => self.key().to_string(),
+        };
+        let rhs = self.rhs();
+        match self.quantifier() {
+            Some(quantifier) => write!(f, "{quantifier} {key}{rhs}"),
+            None => write!(f, "{key}{rhs}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum QueryArgumentExpression {
+    Argument(QueryArgument),
+    Or(Vec<QueryArgumentExpression>),
+    Not(Box<QueryArgumentExpression>),
+    Group(QueryArguments),
+}
+
+impl Display for QueryArgumentExpression {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            QueryArgumentExpression::Argument(argument) => argument.fmt(f),
+            QueryArgumentExpression::Or(expressions) => {
+                let expressions = expressions
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" || ");
+                expressions.fmt(f)
+            }
+            // The `!` operator binds tighter than `||`, so we have to keep the parentheses
+            QueryArgumentExpression::Not(expression) => match expression.as_ref() {
+                QueryArgumentExpression::Or(_) => write!(f, "!({expression})"),
+                _ => write!(f, "!{expression}"),
+            },
+            QueryArgumentExpression::Group(arguments) => arguments.fmt(f),
+        }
+    }
+}
+
+impl<'a> QueryArgumentExpression {
+    fn satisfies(&'a self, value: &Value, context: &Context<'a>) -> Result<bool, Error<'a>> {
+        match self {
+            QueryArgumentExpression::Argument(argument) => argument.satisfies(value, context),
+            // Both `Or` and `Group` are short-circuited, so an error is only returned if
+            // the expression that fails is evaluated
+            QueryArgumentExpression::Or(expressions) => {
+                expressions.iter().try_fold(false, |satisfied, expression| {
+                    Ok(satisfied || expression.satisfies(value, context)?)
+                })
+            }
+            QueryArgumentExpression::Not(expression) => expression
+                .satisfies(value, context)
+                .map(|satisfied| !satisfied),
+            QueryArgumentExpression::Group(arguments) => {
+                arguments.0.iter().try_fold(true, |satisfied, expression| {
+                    Ok(satisfied && expression.satisfies(value, context)?)
+                })
+            }
+        }
     }
 }
 
 #[derive(Debug, Clone, Constructor, Default)]
-pub struct QueryArguments(pub Vec<QueryArgument>);
+pub struct QueryArguments(pub Vec<QueryArgumentExpression>);
 
 impl Display for QueryArguments {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
@@ -457,7 +963,11 @@ impl QueryArguments {
                         error: Box::new(error),
                         context: context.path().clone(),
                     };
-                    log::warn!("{argument_error}");
+                    context.report(
+                        DiagnosticKind::ArgumentError,
+                        context.path(),
+                        &argument_error,
+                    );
                 })
                 .unwrap_or(false)
         })
@@ -470,21 +980,61 @@ impl<'a> QueryArgument {
 
     fn satisfies(&'a self, value: &Value, context: &Context<'a>) -> Result<bool, Error<'a>> {
         let argument_key = self.key();
+        let inspected_value = Self::inspect_or_null(argument_key, value, context)?;
 
-        let inspected_value = match argument_key.inspect(value, context) {
-            Ok(value) => value,
-            // TODO: only return null value for the KeyNotFound error?  Check the test with this TODO at the not_equal.rs test
-
-            // TODO: the query inspection should not use InternalError, it is too generic
-            Err(error @ InternalError::KeyNotFound(_)) => {
-                log::info!("{error}, using null value");
-                Self::DEFAULT_INSPECTED_VALUE
+        let resolved_operation;
+        let operation = match &self.rhs {
+            QueryArgumentRhs::Operation(operation) => operation,
+            // The compared key is inspected in the same element as the argument key
+            QueryArgumentRhs::KeyComparison(operator, compared_key) => {
+                let compared_value = Self::inspect_or_null(compared_key, value, context)?;
+                resolved_operation = operator.operation_with(&compared_value).ok_or_else(|| {
+                    Error::IncompatibleOperation {
+                        value_type: compared_value.value_type(),
+                        operation_type: operator.to_string(),
+                        context: context.push_query_key(compared_key).path().clone(),
+                    }
+                })?;
+                &resolved_operation
             }
-            Err(error) => return Err(error.into()),
         };
 
         let inspected_context = context.push_query_key(argument_key);
-        self.operation
-            .satisfies(&inspected_value, &inspected_context)
+        match (self.quantifier, inspected_value.as_ref()) {
+            (Some(quantifier), Value::Array(array)) => {
+                let satisfies_op =
+                    |item: &Value, context: &Context<'a>| operation.satisfies(item, context);
+                Ok(QueryArgumentOperation::satisfies_op_array(
+                    array,
+                    satisfies_op,
+                    quantifier,
+                    &inspected_context,
+                ))
+            }
+            // Other values are quantified as if they were the only element of an array
+            (Some(Quantifier::None), value) => operation
+                .satisfies(value, &inspected_context)
+                .map(|result| !result),
+            (_, value) => operation.satisfies(value, &inspected_context),
+        }
+    }
+
+    fn inspect_or_null<'b>(
+        key: &'a QueryKey,
+        value: &'b Value,
+        context: &Context<'a>,
+    ) -> Result<Cow<'b, Value>, Error<'a>> {
+        match key.inspect(value, context) {
+            Ok(value) => Ok(value),
+            // TODO: only return null value for the KeyNotFound error?  Check the test with this TODO at the not_equal.rs test
+
+            // TODO: the query inspection should not use InternalError, it is too generic
+            Err(InternalError::KeyNotFound(path)) => {
+                let message = format!("key '{path}' not found, using null value");
+                context.report(DiagnosticKind::NullDefault, &path, &message);
+                Ok(Self::DEFAULT_INSPECTED_VALUE)
+            }
+            Err(error) => Err(error.into()),
+        }
     }
 }
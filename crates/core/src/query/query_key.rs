@@ -1,18 +1,23 @@
 use std::{
     borrow::Cow,
     fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
     ops::Add,
     sync::LazyLock,
 };
 
 use derive_getters::Getters;
 use derive_more::Constructor;
+use logos::Span;
 use regex::Regex;
-use serde_json::Value;
+use serde_json::{Map, Value};
 
 use super::{
-    apply::InternalError, context::Context, query_arguments::QueryArguments,
-    query_operators::QueryOperators,
+    apply::InternalError,
+    context::Context,
+    diagnostic::DiagnosticKind,
+    query_arguments::QueryArguments,
+    query_operators::{QueryOperator, QueryOperators},
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -20,6 +25,43 @@ pub enum RawKey {
     Identifier(String),
     // TODO: think of a better variant name
     String(String),
+    // Matches every key of an object
+    Wildcard,
+    // Matches every key of an object that matches the pattern
+    Pattern(KeyPattern),
+    // Matches the rest of the keys at any depth
+    Descendants,
+}
+
+/// Regex that selects object keys. Patterns are compared by their source, so they can be part
+/// of a `RawKey`.
+#[derive(Debug, Clone)]
+pub struct KeyPattern(pub Regex);
+
+impl PartialEq for KeyPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for KeyPattern {}
+
+impl PartialOrd for KeyPattern {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for KeyPattern {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.as_str().cmp(other.0.as_str())
+    }
+}
+
+impl Hash for KeyPattern {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.as_str().hash(state);
+    }
 }
 
 // IMPORTANT: This regex must exactly match the identifier regex in the lexer. Also,
@@ -28,9 +70,13 @@ pub enum RawKey {
 static IDENTIFIER_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^[a-zA-Z_][\w-]*$").unwrap());
 
+// IMPORTANT: This list must contain every keyword token of the lexer, since those
+// words are never lexed as identifiers and have to be quoted to be used as keys.
+const KEYWORDS: &[&str] = &["true", "false", "null"];
+
 impl From<&str> for RawKey {
     fn from(value: &str) -> Self {
-        if IDENTIFIER_REGEX.is_match(value) {
+        if IDENTIFIER_REGEX.is_match(value) && !KEYWORDS.contains(&value) {
             RawKey::Identifier(value.to_string())
         } else {
             RawKey::String(value.to_string())
@@ -46,6 +92,33 @@ impl RawKey {
         match self {
             RawKey::Identifier(identifier) => identifier,
             RawKey::String(escaped) => escaped,
+            RawKey::Wildcard => "*",
+            RawKey::Pattern(pattern) => pattern.0.as_str(),
+            RawKey::Descendants => "**",
+        }
+    }
+
+    /// Whether this key selects several keys of an object, whose values are output in an
+    /// object with the same keys
+    pub fn is_multiple(&self) -> bool {
+        matches!(self, RawKey::Wildcard | RawKey::Pattern(_))
+    }
+
+    /// Whether a key of an object is selected by this key
+    pub fn matches(&self, key: &str) -> bool {
+        match self {
+            RawKey::Identifier(_) | RawKey::String(_) => self.as_str() == key,
+            RawKey::Wildcard | RawKey::Descendants => true,
+            RawKey::Pattern(pattern) => pattern.0.is_match(key),
+        }
+    }
+
+    /// Whether the object has any key matched by this key
+    pub fn matches_any(&self, object: &Map<String, Value>) -> bool {
+        match self {
+            RawKey::Identifier(_) | RawKey::String(_) => object.contains_key(self.as_str()),
+            RawKey::Wildcard | RawKey::Pattern(_) => object.keys().any(|key| self.matches(key)),
+            RawKey::Descendants => true,
         }
     }
 }
@@ -58,6 +131,12 @@ impl Display for RawKey {
                 let escaped_string = escape8259::escape(unescaped_string);
                 write!(f, "\"{escaped_string}\"")
             }
+            RawKey::Wildcard => '*'.fmt(f),
+            RawKey::Pattern(pattern) => {
+                let escaped_pattern = escape8259::escape(pattern.0.as_str());
+                write!(f, "~\"{escaped_pattern}\"")
+            }
+            RawKey::Descendants => "**".fmt(f),
         }
     }
 }
@@ -66,15 +145,23 @@ impl Display for RawKey {
 pub struct AtomicQueryKey {
     // TODO: rename those attributes?
     key: RawKey,
+    // Optional keys output `null` instead of failing when they are not found
+    optional: bool,
     arguments: QueryArguments,
     // TODO: change this to Vec<QueryOperator> so we can chain operators ([0][0] and etc``)
     operators: QueryOperators,
+    /// Position of the key in the query source
+    span: Span,
 }
 
 impl Display for AtomicQueryKey {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         self.key().fmt(f)?;
 
+        if *self.optional() {
+            write!(f, "?")?;
+        }
+
         let arguments = self.arguments();
         if !arguments.0.is_empty() {
             arguments.fmt(f)?;
@@ -119,6 +206,44 @@ impl<'a> QueryKey {
         self.keys().last().expect("query key cannot be empty")
     }
 
+    /// Position of the whole key in the query source, if it is not empty
+    pub fn span(&self) -> Option<Span> {
+        Self::keys_span(self.keys())
+    }
+
+    fn keys_span(keys: &[AtomicQueryKey]) -> Option<Span> {
+        let (first_key, last_key) = (keys.first()?, keys.last()?);
+        Some(first_key.span().start..last_key.span().end)
+    }
+
+    /// Whether the inspected value may be a `null` that was not in the input, because any of the
+    /// keys is optional or has arguments that an object may not satisfy
+    pub fn is_nullable(&self) -> bool {
+        self.keys()
+            .iter()
+            .any(|key| *key.optional() || !key.arguments().0.is_empty())
+    }
+
+    /// Number of nested objects whose values are selected by this key. Each wildcard selects the
+    /// values of an object, unless they are collected into an array, as does a trailing operator
+    /// that outputs an object of groups.
+    pub fn keyed_levels(&self) -> usize {
+        let wildcards = self
+            .keys()
+            .iter()
+            .enumerate()
+            .filter(|(index, atomic_query_key)| {
+                atomic_query_key.key().is_multiple()
+                    && !Self::collects_values(atomic_query_key, &self.keys()[index + 1..])
+            })
+            .count();
+        let groups = self
+            .keys()
+            .last()
+            .is_some_and(|last_key| last_key.operators().outputs_groups());
+        wildcards + usize::from(groups)
+    }
+
     // TODO: inspect should return the indexed context? in a lot of places we index and then create
     // the indexed context
     // TODO: maybe we should move the InternalError::KeyNotFound to this module? so we are not using something
@@ -133,8 +258,9 @@ impl<'a> QueryKey {
             Cow::Borrowed(value),
             self.keys(),
             &QueryArguments::default(),
-            &QueryOperators::default(),
+            &[],
             context,
+            false,
         )
     }
     pub fn inspect_owned(
@@ -150,7 +276,6 @@ impl<'a> QueryKey {
         )
     }
 
-    // TODO add operators here
     pub fn inspect_owned_with_arguments_and_operator(
         &'a self,
         value: Value,
@@ -162,8 +287,9 @@ impl<'a> QueryKey {
             Cow::Owned(value),
             self.keys(),
             arguments,
-            operators,
+            &operators.0,
             context,
+            false,
         )
         .map(Cow::into_owned)
     }
@@ -173,26 +299,82 @@ impl<'a> QueryKey {
     // when we are given an owned value (for example, inspecting in the root query).
     // We still have the issue then the consumer wants to pass a reference as an input and only needs
     // a reference to the inspected value, not an owned Value (argument filtering).
+    /// `in_mapping` tells whether the value is an item of an array that is being mapped. In that
+    /// case, collecting operators are not applied to the item, as they are applied to the whole
+    /// mapped array by [`QueryKey::do_inspect_array`].
     pub fn do_inspect<'b>(
         value: Cow<'b, Value>,
         keys: &'a [AtomicQueryKey],
         parent_arguments: &QueryArguments,
-        parent_operators: &QueryOperators,
+        parent_operators: &[QueryOperator],
         context: &Context<'a>,
+        in_mapping: bool,
     ) -> Result<Cow<'b, Value>, InternalError<'a>> {
-        let result = match value {
-            Cow::Owned(Value::Object(_)) | Cow::Borrowed(Value::Object(_)) => {
-                Self::do_inspect_object(value, keys, parent_arguments, context)?
-            }
-            Cow::Owned(Value::Array(_)) | Cow::Borrowed(Value::Array(_)) => Cow::Owned(
-                Self::do_inspect_array(value, keys, parent_arguments, context)?,
-            ),
-            value => Self::do_inspect_primitive(value, keys, parent_arguments, context)?,
+        let parent_operators = if in_mapping {
+            QueryOperator::split_collecting(parent_operators).0
+        } else {
+            parent_operators
         };
 
-        let result = parent_operators.apply(result, context)?;
+        // Keyed operators must see the value of the key they are attached to, so the value is
+        // filtered and operated before inspecting the rest of the keys. Otherwise, operators such
+        // as sort would not be able to access fields that are not selected by the rest of the keys.
+        // The remaining operators are applied to the inspected value.
+        let (keyed_operators, rest_operators) = QueryOperator::split_keyed(parent_operators);
+        let result = if keyed_operators.is_empty() {
+            Self::do_inspect_value(value, keys, parent_arguments, context, in_mapping)?
+        } else {
+            let filtered =
+                Self::do_inspect_value(value, &[], parent_arguments, context, in_mapping)?;
+            let operated = QueryOperator::apply_all(keyed_operators, filtered, context)?;
+            Self::do_inspect_value(
+                operated,
+                keys,
+                &QueryArguments::default(),
+                context,
+                in_mapping,
+            )?
+        };
+        Ok(QueryOperator::apply_all(rest_operators, result, context)?)
+    }
 
-        Ok(result)
+    fn do_inspect_value<'b>(
+        value: Cow<'b, Value>,
+        keys: &'a [AtomicQueryKey],
+        parent_arguments: &QueryArguments,
+        context: &Context<'a>,
+        in_mapping: bool,
+    ) -> Result<Cow<'b, Value>, InternalError<'a>> {
+        // Descendants are looked up in the whole value, even if it is an array, so arrays are
+        // not mapped as with the rest of the keys
+        if let Some((atomic_query_key, rest)) = keys.split_first() {
+            if atomic_query_key.key() == &RawKey::Descendants {
+                let filtered = match parent_arguments.0.is_empty() {
+                    true => value,
+                    false => {
+                        Self::do_inspect_value(value, &[], parent_arguments, context, in_mapping)?
+                    }
+                };
+                return Self::do_inspect_descendants(
+                    filtered,
+                    atomic_query_key,
+                    rest,
+                    context,
+                    in_mapping,
+                )
+                .map(Cow::Owned);
+            }
+        }
+
+        match value {
+            Cow::Owned(Value::Object(_)) | Cow::Borrowed(Value::Object(_)) => {
+                Self::do_inspect_object(value, keys, parent_arguments, context, in_mapping)
+            }
+            Cow::Owned(Value::Array(_)) | Cow::Borrowed(Value::Array(_)) => Ok(Cow::Owned(
+                Self::do_inspect_array(value, keys, parent_arguments, context, in_mapping)?,
+            )),
+            value => Self::do_inspect_primitive(value, keys, parent_arguments, context),
+        }
     }
 
     pub fn do_inspect_object<'b>(
@@ -200,12 +382,16 @@ impl<'a> QueryKey {
         keys: &'a [AtomicQueryKey],
         parent_arguments: &QueryArguments,
         context: &Context<'a>,
+        in_mapping: bool,
     ) -> Result<Cow<'b, Value>, InternalError<'a>> {
-        // TODO: maybe this check should be done inside QueryArguments, as we do in query operators
-        if !parent_arguments.0.is_empty() {
-            // TODO: throw an error here or log a warning?
-            // in my opinion we should fail
-            return Err(InternalError::NonFiltrableValue(context.path().clone()));
+        // Objects are kept if they satisfy the arguments and replaced by `null` otherwise, which
+        // short-circuits the rest of the keys as optional keys do. Inside a mapping, the mapped
+        // element is filtered out instead, as the elements of arrays are.
+        if !parent_arguments.0.is_empty() && !parent_arguments.satisfies(&value, context) {
+            if in_mapping {
+                return Err(InternalError::UnsatisfiedArguments(context.path().clone()));
+            }
+            return Ok(Cow::Owned(Value::Null));
         }
 
         let Some((atomic_query_key, rest)) = keys.split_first() else {
@@ -215,7 +401,21 @@ impl<'a> QueryKey {
         let raw_key = atomic_query_key.key();
         let arguments = atomic_query_key.arguments();
         let query_operators = atomic_query_key.operators();
-        let new_context = context.push_raw_key(raw_key);
+
+        if raw_key.is_multiple() {
+            return Self::do_inspect_multiple(
+                value,
+                raw_key,
+                rest,
+                arguments,
+                &query_operators.0,
+                &context.with_span(Self::keys_span(keys)),
+                in_mapping,
+            )
+            .map(Cow::Owned);
+        }
+
+        let new_context = context.push_atomic_key(atomic_query_key);
 
         let current = match value {
             Cow::Owned(Value::Object(mut object)) => object
@@ -227,27 +427,240 @@ impl<'a> QueryKey {
                 .get(raw_key.as_str())
                 .map(Cow::Borrowed),
             _ => unreachable!("In this match branch there are only Value::Object variants"),
+        };
+
+        let Some(current) = current else {
+            if *atomic_query_key.optional() {
+                let path = new_context.path();
+                let message = format!("key '{path}' not found, using null value");
+                new_context.report(DiagnosticKind::NullDefault, path, &message);
+                return Ok(Cow::Owned(Value::Null));
+            }
+            return Err(InternalError::KeyNotFound(new_context.path().clone()));
+        };
+
+        // As missing keys, `null` values of optional keys short-circuit the rest of the keys
+        if *atomic_query_key.optional() && current.is_null() {
+            return Ok(current);
+        }
+
+        Self::do_inspect(
+            current,
+            rest,
+            arguments,
+            &query_operators.0,
+            &new_context,
+            in_mapping,
+        )
+    }
+
+    /// Looks up the keys at any depth of the value. Every object (including the value itself) that
+    /// has the first key is inspected as an item of an array, in document order.
+    fn do_inspect_descendants(
+        value: Cow<Value>,
+        descendants_key: &'a AtomicQueryKey,
+        keys: &'a [AtomicQueryKey],
+        context: &Context<'a>,
+        in_mapping: bool,
+    ) -> Result<Value, InternalError<'a>> {
+        // Descendants are borrowed from the value, so only the inspected results are cloned
+        let mut descendants = Vec::new();
+        Self::collect_descendants(&value, keys.first(), &mut descendants);
+
+        let descendants_context = context.push_atomic_key(descendants_key);
+        Self::do_inspect_items(
+            descendants.into_iter().map(Cow::Borrowed),
+            keys,
+            &QueryArguments::default(),
+            &descendants_context,
+            in_mapping,
+        )
+    }
+
+    fn collect_descendants<'b>(
+        value: &'b Value,
+        next_key: Option<&AtomicQueryKey>,
+        descendants: &mut Vec<&'b Value>,
+    ) {
+        match value {
+            Value::Object(object) => {
+                if next_key.map_or(true, |next_key| next_key.key().matches_any(object)) {
+                    descendants.push(value);
+                }
+                object
+                    .values()
+                    .for_each(|item| Self::collect_descendants(item, next_key, descendants));
+            }
+            Value::Array(array) => array
+                .iter()
+                .for_each(|item| Self::collect_descendants(item, next_key, descendants)),
+            _ => (),
+        }
+    }
+
+    /// Whether the values selected by a multiple key are collected into an array, which happens
+    /// when it has operators or when one of the following keys has a collecting operator
+    fn collects_values(multiple_key: &AtomicQueryKey, keys: &[AtomicQueryKey]) -> bool {
+        !multiple_key.operators().0.is_empty()
+            || keys.iter().any(|key| key.operators().is_collecting())
+    }
+
+    /// Inspects the keys in every value of the object selected by the raw key, keeping their keys.
+    /// As in arrays, the arguments filter the values and an error only discards the value that
+    /// caused it.
+    ///
+    /// The values are mapped as the items of an array when they have to be collected: the
+    /// operators of the raw key are applied to the array of values, and a collecting key stops the
+    /// mapping as described in [`QueryKey::do_inspect_array`]. Their keys are lost in that case.
+    fn do_inspect_multiple<'b>(
+        value: Cow<'b, Value>,
+        raw_key: &RawKey,
+        keys: &'a [AtomicQueryKey],
+        arguments: &QueryArguments,
+        operators: &[QueryOperator],
+        context: &Context<'a>,
+        in_mapping: bool,
+    ) -> Result<Value, InternalError<'a>> {
+        let object_context = context.enter_array();
+
+        let object_iter: Box<dyn Iterator<Item = (String, Cow<Value>)>> = match value {
+            Cow::Owned(Value::Object(object)) => Box::new(
+                object
+                    .into_iter()
+                    .map(|(key, item)| (key, Cow::Owned(item))),
+            ),
+            Cow::Borrowed(Value::Object(object)) => Box::new(
+                object
+                    .iter()
+                    .map(|(key, item)| (key.clone(), Cow::Borrowed(item))),
+            ),
+            _ => unreachable!("Multiple keys are only inspected in Value::Object variants"),
+        };
+
+        let matched_items = object_iter
+            .filter(|(key, _)| raw_key.matches(key))
+            .map(|(key, item)| (object_context.push_object_key(key.clone()), key, item))
+            .filter(|(item_context, _, item)| arguments.satisfies(item, item_context));
+
+        // As in nested arrays, the collecting operators of a mapping are applied to the whole
+        // mapped array, so they are not applied to the values
+        let operators = match in_mapping {
+            true => QueryOperator::split_collecting(operators).0,
+            false => operators,
+        };
+        let collecting_key_index = keys
+            .iter()
+            .position(|key| key.operators().is_collecting())
+            .filter(|_| !in_mapping);
+        if !operators.is_empty() || collecting_key_index.is_some() {
+            let mapped_keys = match collecting_key_index {
+                Some(index) if operators.is_empty() => &keys[..=index],
+                _ => &[],
+            };
+            let values = matched_items
+                .map(|(item_context, _, item)| {
+                    Self::do_inspect(
+                        item,
+                        mapped_keys,
+                        &QueryArguments::default(),
+                        &[],
+                        &item_context,
+                        true,
+                    )
+                    .map(Cow::into_owned)
+                })
+                .filter_map(|result| object_context.array_item_result(result))
+                .collect::<Result<_, _>>()?;
+            let values = Cow::Owned(Value::Array(values));
+
+            let result = match collecting_key_index {
+                Some(index) if operators.is_empty() => {
+                    let (_, collecting_operators) =
+                        QueryOperator::split_collecting(&keys[index].operators().0);
+                    let collected =
+                        QueryOperator::apply_all(collecting_operators, values, context)?;
+                    Self::do_inspect(
+                        collected,
+                        &keys[index + 1..],
+                        &QueryArguments::default(),
+                        &[],
+                        context,
+                        false,
+                    )
+                }
+                _ => Self::do_inspect(
+                    values,
+                    keys,
+                    &QueryArguments::default(),
+                    operators,
+                    context,
+                    in_mapping,
+                ),
+            };
+            return result.map(Cow::into_owned);
         }
-        .ok_or(InternalError::KeyNotFound(new_context.path().clone()))?;
 
-        Self::do_inspect(current, rest, arguments, query_operators, &new_context)
+        let result = matched_items
+            .map(|(item_context, key, item)| {
+                Self::do_inspect(
+                    item,
+                    keys,
+                    &QueryArguments::default(),
+                    operators,
+                    &item_context,
+                    in_mapping,
+                )
+                .map(|value| (key, value.into_owned()))
+            })
+            .filter_map(|result| object_context.array_item_result(result))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Value::Object(result))
     }
+
+    /// Maps the keys over the items of the array. If a key has a collecting operator (an
+    /// aggregation, for example), the mapping stops at that key, the collecting operators are
+    /// applied to the whole mapped array and the rest of the keys are inspected in the result.
+    /// Nested mappings are part of the outermost one, so they never collect by themselves.
     pub fn do_inspect_array<'b>(
         value: Cow<'b, Value>,
         keys: &'a [AtomicQueryKey],
         parent_arguments: &QueryArguments,
         context: &Context<'a>,
+        in_mapping: bool,
     ) -> Result<Value, InternalError<'a>> {
-        let array_context = context.enter_array();
-
         // TODO: think if there is a better way to do this, but I think this is the best we can do
         let array_iter: Box<dyn Iterator<Item = Cow<Value>>> = match value {
             Cow::Owned(Value::Array(array)) => Box::new(array.into_iter().map(Cow::Owned)),
             Cow::Borrowed(Value::Array(array)) => Box::new(array.iter().map(Cow::Borrowed)),
             _ => unreachable!("In this match branch there are only Value::Array variants"),
         };
+        Self::do_inspect_items(array_iter, keys, parent_arguments, context, in_mapping)
+    }
 
-        let result = array_iter
+    /// Maps the keys over the items as described in [`QueryKey::do_inspect_array`]. Descendants are
+    /// mapped with it too, so they do not have to be cloned into an array.
+    fn do_inspect_items<'b>(
+        items: impl Iterator<Item = Cow<'b, Value>>,
+        keys: &'a [AtomicQueryKey],
+        parent_arguments: &QueryArguments,
+        context: &Context<'a>,
+        in_mapping: bool,
+    ) -> Result<Value, InternalError<'a>> {
+        let collecting_key_index = keys
+            .iter()
+            .position(|key| key.operators().is_collecting())
+            .filter(|_| !in_mapping);
+        let (mapped_keys, rest) = match collecting_key_index {
+            Some(index) => keys.split_at(index + 1),
+            None => (keys, &[][..]),
+        };
+        // Errors of the items come from the keys mapped over them
+        let array_context = context
+            .enter_array()
+            .with_span(Self::keys_span(mapped_keys).or_else(|| context.span().clone()));
+
+        let result = items
             .enumerate()
             .map(|(index, item)| (array_context.push_index(index), item))
             .filter(|(item_context, item)| parent_arguments.satisfies(item, item_context))
@@ -262,28 +675,40 @@ impl<'a> QueryKey {
                 };
                 Self::do_inspect(
                     item,
-                    keys,
+                    mapped_keys,
                     arguments_to_propagate,
-                    &QueryOperators::default(),
+                    &[],
                     &item_context,
+                    true,
                 )
             })
-            .flat_map(|result| {
-                result
-                    .map_err(|error| {
-                        let array_error = InternalError::InsideArray(
-                            Box::new(error),
-                            array_context.path().clone(),
-                        );
-                        log::warn!("{array_error}");
-                    })
-                    .ok()
-            })
             // We have to own the values if we want to return a Value::Array
-            .map(Cow::into_owned)
-            .collect();
+            .map(|result| result.map(Cow::into_owned))
+            .filter_map(|result| array_context.array_element_result(result))
+            .collect::<Result<_, _>>()?;
 
-        Ok(Value::Array(result))
+        let Some(collecting_key_index) = collecting_key_index else {
+            return Ok(Value::Array(result));
+        };
+
+        let collecting_key = &keys[collecting_key_index];
+        let (_, collecting_operators) =
+            QueryOperator::split_collecting(&collecting_key.operators().0);
+        let collected = QueryOperator::apply_all(
+            collecting_operators,
+            Cow::Owned(Value::Array(result)),
+            context,
+        )?;
+
+        Self::do_inspect(
+            collected,
+            rest,
+            &QueryArguments::default(),
+            &[],
+            context,
+            false,
+        )
+        .map(Cow::into_owned)
     }
 
     pub fn do_inspect_primitive<'b>(
@@ -295,9 +720,11 @@ impl<'a> QueryKey {
         if !parent_arguments.0.is_empty() {
             return Err(InternalError::NonFiltrableValue(context.path().clone()));
         }
-        if !keys.is_empty() {
-            return Err(InternalError::NonIndexableValue(context.path().clone()));
+        match (keys.first(), value.as_ref()) {
+            (None, _) => Ok(value),
+            // Optional keys of `null` values are missing as well
+            (Some(atomic_query_key), Value::Null) if *atomic_query_key.optional() => Ok(value),
+            (Some(_), _) => Err(InternalError::NonIndexableValue(context.path().clone())),
         }
-        Ok(value)
     }
 }
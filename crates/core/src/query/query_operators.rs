@@ -1,12 +1,28 @@
-use std::{borrow::Cow, fmt::Display, ops::RangeInclusive};
+use std::{borrow::Cow, fmt::Display};
 
 use derive_more::Constructor;
 use serde_json::Value;
 use thiserror::Error;
 
-use super::context::Context;
+use super::{
+    apply::InternalError,
+    context::{Context, OwnedJsonPath},
+    diagnostic::DiagnosticKind,
+    query_key::QueryKey,
+};
 use crate::query::query_arguments::ValueType;
 
+mod aggregation;
+mod flatten;
+mod group_by;
+mod indexing;
+mod sort;
+mod unique;
+
+pub use self::aggregation::Aggregation;
+pub use self::indexing::{IndexingRange, IndexingValue, OutOfBounds};
+pub use self::sort::{cmp_values, SortKey, SortOrder};
+
 #[derive(Error, Debug, Clone)]
 pub enum Error {
     #[error("'{}' operator is not supported for '{value_type}' type",query_operator.operator_type())]
@@ -14,79 +30,124 @@ pub enum Error {
         query_operator: QueryOperator,
         value_type: String,
     },
+    #[error("'[{indexing_value}]' is out of bounds for an array of length {length} at '{path}'")]
+    IndexOutOfBounds {
+        indexing_value: IndexingValue,
+        length: usize,
+        path: OwnedJsonPath,
+    },
+    #[error("{error} while applying '{}' operator", query_operator.operator_type())]
+    InsideOperator {
+        query_operator: QueryOperator,
+        error: Box<super::apply::Error>,
+    },
 }
 
 #[derive(Debug, Clone)]
-pub enum IndexingValue {
-    // TODO: use `std::ops::RangeBounds` instead of `Range` in order to be more generic?
-    Range(RangeInclusive<usize>),
-    Index(usize),
+pub enum QueryOperator {
+    // TODO: support for indexing with strings? ["key"]? Does this makes sense in our case?
+    //TODO: should query arguments be a type of QueryOperator? so we can to something like
+    // {query_key[0](key="x")}
+    Indexing(IndexingValue),
+    // Sorts an array by the given keys. If there are no keys, items are sorted by themselves
+    Sort(Vec<SortKey>),
+    // Reduces an array into a single value
+    Aggregation(Aggregation),
+    // Groups the items of an array into an object keyed by the value at the given key
+    GroupBy(QueryKey),
+    // Removes duplicated items of an array, compared by themselves or by the given key
+    Unique(Option<QueryKey>),
+    // Flattens the nested arrays of an array up to the given depth
+    Flatten(usize),
 }
 
-impl IndexingValue {
-    // TODO: maybe we should return Result for out of bounds handling
-    pub fn apply<'a>(&self, array: Cow<'a, Vec<Value>>) -> Cow<'a, Value> {
+impl QueryOperator {
+    pub const DEFAULT_FLATTEN_DEPTH: usize = 1;
+
+    pub fn operator_type(&self) -> &str {
         match self {
-            Self::Range(range) => {
-                let result = match array {
-                    Cow::Owned(mut array) => array.drain(range.clone()).collect::<Vec<Value>>(),
-                    Cow::Borrowed(array) => array
-                        .get(range.clone())
-                        .expect("TODO: handle range out of bounds")
-                        // Note that this `to_vec` clones the underlying `Values`
-                        .to_vec(),
-                };
-                Cow::Owned(Value::Array(result))
-            }
-            Self::Index(index) => {
-                match array {
-                    Cow::Owned(mut array) => {
-                        // TODO: handle out of bounds here
-                        let indexed = array.remove(*index);
-                        Cow::Owned(indexed)
-                    }
-                    Cow::Borrowed(array) => {
-                        // TODO: handle out of bounds
-                        let indexed = array.get(*index).expect("out of bounds");
-                        Cow::Borrowed(indexed)
-                    }
-                }
-            }
+            Self::Indexing(_) => "indexing",
+            Self::Sort(_) => "sort",
+            Self::Aggregation(aggregation) => aggregation.name(),
+            Self::GroupBy(_) => "groupBy",
+            Self::Unique(None) => "unique",
+            Self::Unique(Some(_)) => "uniqueBy",
+            Self::Flatten(_) => "flatten",
         }
     }
-}
 
-impl Display for IndexingValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// Collecting operators are applied to the whole array when the key they are attached to is
+    /// reached through an array mapping (`orders.total[sum]`), instead of to each mapped item.
+    pub fn is_collecting(&self) -> bool {
         match self {
-            Self::Range(range) => write!(f, "{}..{}", range.start(), range.end()),
-            Self::Index(index) => index.fmt(f),
+            Self::Indexing(_) | Self::Sort(_) | Self::GroupBy(_) => false,
+            Self::Aggregation(_) | Self::Unique(_) | Self::Flatten(_) => true,
         }
     }
-}
 
-#[derive(Debug, Clone)]
-pub enum QueryOperator {
-    // TODO: support for indexing with strings? ["key"]? Does this makes sense in our case?
-    //TODO: should query arguments be a type of QueryOperator? so we can to something like
-    // {query_key[0](key="x")}
-    Indexing(IndexingValue),
-}
+    /// Splits the operators before the first collecting operator
+    pub fn split_collecting(operators: &[Self]) -> (&[Self], &[Self]) {
+        let index = operators
+            .iter()
+            .position(Self::is_collecting)
+            .unwrap_or(operators.len());
+        operators.split_at(index)
+    }
 
-impl QueryOperator {
-    pub fn operator_type(&self) -> &str {
+    /// Keyed operators look up their keys in the items of the array, so they have to be applied
+    /// before the rest of the keys select the fields of those items.
+    pub fn is_keyed(&self) -> bool {
         match self {
-            Self::Indexing(_) => "indexing",
+            Self::Sort(sort_keys) => !sort_keys.is_empty(),
+            Self::GroupBy(_) | Self::Unique(Some(_)) => true,
+            Self::Indexing(_) | Self::Aggregation(_) | Self::Unique(None) | Self::Flatten(_) => {
+                false
+            }
         }
     }
+
+    /// Splits the operators after the last keyed operator
+    pub fn split_keyed(operators: &[Self]) -> (&[Self], &[Self]) {
+        let index = operators
+            .iter()
+            .rposition(Self::is_keyed)
+            .map_or(0, |index| index + 1);
+        operators.split_at(index)
+    }
+
+    pub fn apply_all<'a>(
+        operators: &[Self],
+        value: Cow<'a, Value>,
+        context: &Context,
+    ) -> Result<Cow<'a, Value>, Error> {
+        operators
+            .iter()
+            .try_fold(value, |value, operator| operator.apply(value, context))
+    }
+
     pub fn apply<'a>(
         &self,
         value: Cow<'a, Value>,
-        // TODO: use context or remove it
-        _context: &Context,
+        context: &Context,
     ) -> Result<Cow<'a, Value>, Error> {
         match self {
-            Self::Indexing(indexing_value) => self.apply_indexing(indexing_value, value),
+            Self::Indexing(indexing_value) => self.apply_indexing(indexing_value, value, context),
+            Self::Sort(sort_keys) => self.apply_sort(sort_keys, value, context),
+            Self::Aggregation(aggregation) => self.apply_aggregation(aggregation, value),
+            Self::GroupBy(group_key) => self.apply_group_by(group_key, value, context),
+            Self::Unique(unique_key) => self.apply_unique(unique_key.as_ref(), value, context),
+            Self::Flatten(depth) => self.apply_flatten(*depth, value),
+        }
+    }
+
+    fn expect_array<'a>(&self, value: Cow<'a, Value>) -> Result<Cow<'a, Vec<Value>>, Error> {
+        match value {
+            Cow::Owned(Value::Array(array)) => Ok(Cow::Owned(array)),
+            Cow::Borrowed(Value::Array(array)) => Ok(Cow::Borrowed(array)),
+            value => Err(Error::UnsupportedType {
+                query_operator: self.clone(),
+                value_type: value.value_type(),
+            }),
         }
     }
 
@@ -94,19 +155,92 @@ impl QueryOperator {
         &self,
         indexing_value: &IndexingValue,
         value: Cow<'a, Value>,
+        context: &Context,
     ) -> Result<Cow<'a, Value>, Error> {
-        let array = match value {
-            Cow::Owned(Value::Array(array)) => Cow::Owned(array),
-            Cow::Borrowed(Value::Array(array)) => Cow::Borrowed(array),
-            value => {
-                return Err(Error::UnsupportedType {
-                    query_operator: self.clone(),
-                    value_type: value.value_type(),
-                })
-            }
-        };
+        let array = self.expect_array(value)?;
+        indexing_value.apply(array, context)
+    }
 
-        Ok(indexing_value.apply(array))
+    fn apply_sort<'a>(
+        &self,
+        sort_keys: &[SortKey],
+        value: Cow<'a, Value>,
+        context: &Context,
+    ) -> Result<Cow<'a, Value>, Error> {
+        let array = self.expect_array(value)?;
+        let sorted =
+            sort::sort(sort_keys, array, context).map_err(|error| self.inside_error(error))?;
+        Ok(Cow::Owned(Value::Array(sorted)))
+    }
+
+    fn apply_aggregation<'a>(
+        &self,
+        aggregation: &Aggregation,
+        value: Cow<'a, Value>,
+    ) -> Result<Cow<'a, Value>, Error> {
+        let array = self.expect_array(value)?;
+        aggregation.apply(&array).map(Cow::Owned)
+    }
+
+    fn apply_group_by<'a>(
+        &self,
+        group_key: &QueryKey,
+        value: Cow<'a, Value>,
+        context: &Context,
+    ) -> Result<Cow<'a, Value>, Error> {
+        let array = self.expect_array(value)?;
+        let groups = group_by::group_by(group_key, array, context)
+            .map_err(|error| self.inside_error(error))?;
+        Ok(Cow::Owned(Value::Object(groups)))
+    }
+
+    fn apply_unique<'a>(
+        &self,
+        unique_key: Option<&QueryKey>,
+        value: Cow<'a, Value>,
+        context: &Context,
+    ) -> Result<Cow<'a, Value>, Error> {
+        let array = self.expect_array(value)?;
+        let unique =
+            unique::unique(unique_key, array, context).map_err(|error| self.inside_error(error))?;
+        Ok(Cow::Owned(Value::Array(unique)))
+    }
+
+    fn apply_flatten<'a>(
+        &self,
+        depth: usize,
+        value: Cow<'a, Value>,
+    ) -> Result<Cow<'a, Value>, Error> {
+        let array = self.expect_array(value)?;
+        let flattened = flatten::flatten(array.into_owned(), depth);
+        Ok(Cow::Owned(Value::Array(flattened)))
+    }
+
+    fn inside_error(&self, error: InternalError) -> Error {
+        Error::InsideOperator {
+            query_operator: self.clone(),
+            error: Box::new(error.into()),
+        }
+    }
+}
+
+// TODO: Maybe we should fail instead of defaulting to NULL, as query arguments do.
+const DEFAULT_INSPECTED_VALUE: Value = Value::Null;
+
+/// Inspects the key of an item that is being operated, defaulting to `null` if it is not found
+fn inspect_or_null<'a>(
+    key: &'a QueryKey,
+    item: &Value,
+    context: &Context<'a>,
+) -> Result<Value, InternalError<'a>> {
+    match key.inspect(item, context) {
+        Ok(value) => Ok(value.into_owned()),
+        Err(InternalError::KeyNotFound(path)) => {
+            let message = format!("key '{path}' not found, using null value");
+            context.report(DiagnosticKind::NullDefault, &path, &message);
+            Ok(DEFAULT_INSPECTED_VALUE)
+        }
+        Err(error) => Err(error),
     }
 }
 
@@ -114,6 +248,21 @@ impl Display for QueryOperator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Indexing(indexing_value) => write!(f, "[{indexing_value}]"),
+            Self::Sort(sort_keys) if sort_keys.is_empty() => write!(f, "[sort]"),
+            Self::Sort(sort_keys) => {
+                let sort_keys = sort_keys
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "[sort({sort_keys})]")
+            }
+            Self::Aggregation(aggregation) => write!(f, "[{aggregation}]"),
+            Self::GroupBy(group_key) => write!(f, "[groupBy({group_key})]"),
+            Self::Unique(None) => write!(f, "[unique]"),
+            Self::Unique(Some(unique_key)) => write!(f, "[uniqueBy({unique_key})]"),
+            Self::Flatten(Self::DEFAULT_FLATTEN_DEPTH) => write!(f, "[flatten]"),
+            Self::Flatten(depth) => write!(f, "[flatten({depth})]"),
         }
     }
 }
@@ -127,10 +276,17 @@ impl QueryOperators {
         value: Cow<'a, Value>,
         context: &Context,
     ) -> Result<Cow<'a, Value>, Error> {
-        // TODO: handle context
-        self.0
-            .iter()
-            .try_fold(value, |value, operator| operator.apply(value, context))
+        QueryOperator::apply_all(&self.0, value, context)
+    }
+
+    pub fn is_collecting(&self) -> bool {
+        self.0.iter().any(QueryOperator::is_collecting)
+    }
+
+    /// Whether the operated value is an object of groups, whose values have to be projected
+    /// instead of the object itself
+    pub fn outputs_groups(&self) -> bool {
+        matches!(self.0.last(), Some(QueryOperator::GroupBy(_)))
     }
 }
 
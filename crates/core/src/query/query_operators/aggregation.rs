@@ -0,0 +1,113 @@
+use std::fmt::{self, Display, Formatter};
+
+use serde_json::{Number, Value};
+
+use super::{cmp_values, Error, QueryOperator};
+use crate::query::query_arguments::ValueType;
+
+/// Operators that reduce an array into a single value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl Display for Aggregation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+impl Aggregation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Count => "count",
+            Self::Sum => "sum",
+            Self::Avg => "avg",
+            Self::Min => "min",
+            Self::Max => "max",
+        }
+    }
+
+    /// Reduces the array into a single value. Every aggregation but `count` expects an array of
+    /// numbers. `avg`, `min` and `max` output `null` for empty arrays, while `sum` outputs `0`.
+    pub fn apply(&self, array: &[Value]) -> Result<Value, Error> {
+        match self {
+            Self::Count => Ok(Value::from(array.len())),
+            Self::Sum => self.sum(array),
+            Self::Avg => self.avg(array),
+            // Numbers are compared as in sorting, but other values are not allowed
+            Self::Min => self
+                .expect_numbers(array)
+                .map(|_| Self::extreme(array.iter().min_by(|lhs, rhs| cmp_values(lhs, rhs)))),
+            Self::Max => self
+                .expect_numbers(array)
+                .map(|_| Self::extreme(array.iter().max_by(|lhs, rhs| cmp_values(lhs, rhs)))),
+        }
+    }
+
+    fn extreme(value: Option<&Value>) -> Value {
+        value.cloned().unwrap_or(Value::Null)
+    }
+
+    fn sum(&self, array: &[Value]) -> Result<Value, Error> {
+        let numbers = self.expect_numbers(array)?;
+
+        // Integers are summed in an i128, which cannot overflow when adding u64 and i64 values,
+        // so the sum is exact as long as it fits in a JSON number. Floats are summed as floats.
+        let integer_sum = numbers.iter().try_fold(0i128, |sum, number| {
+            let integer = number
+                .as_i64()
+                .map(i128::from)
+                .or_else(|| number.as_u64().map(i128::from))?;
+            Some(sum + integer)
+        });
+        match integer_sum {
+            Some(sum) => Ok(Self::integer_value(sum)),
+            None => Ok(Value::from(Self::float_sum(&numbers))),
+        }
+    }
+
+    fn integer_value(integer: i128) -> Value {
+        if let Ok(integer) = i64::try_from(integer) {
+            Value::from(integer)
+        } else if let Ok(integer) = u64::try_from(integer) {
+            Value::from(integer)
+        } else {
+            Value::from(integer as f64)
+        }
+    }
+
+    fn avg(&self, array: &[Value]) -> Result<Value, Error> {
+        let numbers = self.expect_numbers(array)?;
+        if numbers.is_empty() {
+            return Ok(Value::Null);
+        }
+        Ok(Value::from(
+            Self::float_sum(&numbers) / numbers.len() as f64,
+        ))
+    }
+
+    fn float_sum(numbers: &[&Number]) -> f64 {
+        numbers
+            .iter()
+            .filter_map(|number| number.as_f64())
+            .sum::<f64>()
+    }
+
+    fn expect_numbers<'a>(&self, array: &'a [Value]) -> Result<Vec<&'a Number>, Error> {
+        array
+            .iter()
+            .map(|item| match item {
+                Value::Number(number) => Ok(number),
+                item => Err(Error::UnsupportedType {
+                    query_operator: QueryOperator::Aggregation(*self),
+                    value_type: item.value_type(),
+                }),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,17 @@
+use serde_json::Value;
+
+/// Flattens the nested arrays of the array up to the given depth. Items that are not arrays are
+/// kept as they are.
+pub fn flatten(array: Vec<Value>, depth: usize) -> Vec<Value> {
+    if depth == 0 {
+        return array;
+    }
+
+    array
+        .into_iter()
+        .flat_map(|item| match item {
+            Value::Array(nested) => flatten(nested, depth - 1),
+            item => vec![item],
+        })
+        .collect()
+}
@@ -0,0 +1,62 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use serde_json::{Map, Value};
+
+use super::inspect_or_null;
+use crate::query::{
+    apply::InternalError, context::Context, diagnostic::DiagnosticKind, query_key::QueryKey,
+};
+
+/// Groups the items of the array by the value at the given key. Groups are keyed by the string
+/// itself for string values and by their JSON representation otherwise, and they keep the order
+/// in which their first item appears in the array. Values of different types may have the same
+/// group name, such as `1` and `"1"`, in which case they are merged and reported.
+pub fn group_by<'a>(
+    group_key: &'a QueryKey,
+    array: Cow<Vec<Value>>,
+    context: &Context<'a>,
+) -> Result<Map<String, Value>, InternalError<'a>> {
+    let array_context = context.enter_array();
+    let group_values = array
+        .iter()
+        .enumerate()
+        .map(|(index, item)| inspect_or_null(group_key, item, &array_context.push_index(index)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut groups = Map::new();
+    // Value of the first item of each group, to detect different values with the same name
+    let mut group_firsts = HashMap::new();
+    for (index, (group_value, item)) in group_values.into_iter().zip(array.into_owned()).enumerate()
+    {
+        let group_name = group_name(&group_value);
+        match group_firsts.get(&group_name) {
+            Some(first_value) if *first_value != group_value => {
+                let item_context = array_context.push_index(index);
+                let message = format!(
+                    "value {group_value} is merged with value {first_value} in group '{group_name}'"
+                );
+                item_context.report(DiagnosticKind::MergedGroup, item_context.path(), &message);
+            }
+            Some(_) => (),
+            None => {
+                group_firsts.insert(group_name.clone(), group_value);
+            }
+        }
+
+        let group = groups
+            .entry(group_name)
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if let Value::Array(group) = group {
+            group.push(item);
+        }
+    }
+
+    Ok(groups)
+}
+
+fn group_name(group_value: &Value) -> String {
+    match group_value {
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
@@ -0,0 +1,236 @@
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    num::NonZeroIsize,
+    ops::{Bound, Range, RangeBounds},
+};
+
+use derive_more::Constructor;
+use serde_json::Value;
+
+use super::Error;
+use crate::query::context::{Context, OwnedJsonPath};
+
+/// Behaviour of the indexing operator when the index or range is out of the bounds of the array
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutOfBounds {
+    /// Fail with an `Error::IndexOutOfBounds`
+    #[default]
+    Error,
+    /// Output `null` instead of the indexed value
+    Null,
+    /// Clamp ranges to the bounds of the array. Single indexes output `null`
+    Clamp,
+}
+
+/// A range of array positions. Ranges always include their start (if any), since there is no
+/// syntax for excluded starts.
+#[derive(Debug, Clone, Constructor)]
+pub struct IndexingRange {
+    start: Option<isize>,
+    end: Bound<isize>,
+}
+
+impl RangeBounds<isize> for IndexingRange {
+    fn start_bound(&self) -> Bound<&isize> {
+        self.start
+            .as_ref()
+            .map_or(Bound::Unbounded, Bound::Included)
+    }
+
+    fn end_bound(&self) -> Bound<&isize> {
+        self.end.as_ref()
+    }
+}
+
+impl IndexingRange {
+    /// Resolves the (possibly negative) bounds of this range against an array of the given length.
+    /// Returns `None` if the range does not fit inside the array. Ranges whose start is after their
+    /// end are resolved as empty.
+    pub fn resolve(&self, length: usize) -> Option<Range<usize>> {
+        let start = match self.start {
+            Some(start) => IndexingValue::resolve_position(start, length)?,
+            None => 0,
+        };
+        let end = match self.end {
+            Bound::Included(end) => IndexingValue::resolve_position(end, length)? + 1,
+            Bound::Excluded(end) => IndexingValue::resolve_position(end, length)?,
+            Bound::Unbounded => length,
+        };
+
+        (start <= length && end <= length).then_some(start.min(end)..end)
+    }
+
+    /// Resolves a range whose start is after its end against an array of the given length, as
+    /// `4..0` in `[4..0:-1]`. Returns the positions from the end to the start, both included, or
+    /// `None` if the range is not descending or the start is not inside the array.
+    pub fn resolve_descending(&self, length: usize) -> Option<Range<usize>> {
+        let start = IndexingValue::resolve_index(self.start?, length)?;
+        let end = match self.end {
+            Bound::Included(end) => IndexingValue::resolve_position(end, length)?,
+            Bound::Excluded(end) => IndexingValue::resolve_position(end, length)? + 1,
+            Bound::Unbounded => return None,
+        };
+        (end <= start).then_some(end..start + 1)
+    }
+
+    /// Resolves the (possibly negative) bounds of this range against an array of the given length,
+    /// clamping them to the array bounds. Ranges whose start is after their end are resolved as empty.
+    pub fn resolve_clamped(&self, length: usize) -> Range<usize> {
+        let clamp_position = |position: isize| match usize::try_from(position) {
+            Ok(position) => position.min(length),
+            Err(_) => length.saturating_sub(position.unsigned_abs()),
+        };
+
+        let start = self.start.map_or(0, clamp_position);
+        let end = match self.end {
+            // An included end before the start of the array must not include the first element
+            Bound::Included(end) if end < 0 && end.unsigned_abs() > length => 0,
+            Bound::Included(end) => (clamp_position(end) + 1).min(length),
+            Bound::Excluded(end) => clamp_position(end),
+            Bound::Unbounded => length,
+        };
+
+        start.min(end)..end
+    }
+}
+
+impl Display for IndexingRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(start) = self.start {
+            start.fmt(f)?;
+        }
+        "..".fmt(f)?;
+        match self.end {
+            Bound::Included(end) => end.fmt(f),
+            Bound::Excluded(end) => write!(f, "<{end}"),
+            Bound::Unbounded => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum IndexingValue {
+    Range(IndexingRange),
+    // Takes every `step` element of the range. Negative steps traverse the range backwards, from
+    // its start down to its end if the start is after the end.
+    SteppedRange(IndexingRange, NonZeroIsize),
+    // Negative indexes are counted from the end of the array, so `-1` is the last element
+    Index(isize),
+}
+
+impl IndexingValue {
+    /// Resolves a (possibly negative) position against an array of the given length.
+    /// Note that the returned position may be equal to `length`, so callers must check
+    /// whether it is a valid index.
+    fn resolve_position(position: isize, length: usize) -> Option<usize> {
+        match usize::try_from(position) {
+            Ok(position) => Some(position),
+            Err(_) => length.checked_sub(position.unsigned_abs()),
+        }
+    }
+
+    fn resolve_index(index: isize, length: usize) -> Option<usize> {
+        Self::resolve_position(index, length).filter(|index| *index < length)
+    }
+
+    pub fn apply<'a>(
+        &self,
+        array: Cow<'a, Vec<Value>>,
+        context: &Context,
+    ) -> Result<Cow<'a, Value>, Error> {
+        let null = Cow::Owned(Value::Null);
+
+        match self {
+            Self::Range(range) => {
+                let Some(range) = self.resolve_range(range, array.len(), context)? else {
+                    return Ok(null);
+                };
+                let result = match array {
+                    Cow::Owned(mut array) => array.drain(range).collect::<Vec<Value>>(),
+                    // Note that this `to_vec` clones the underlying `Values`
+                    Cow::Borrowed(array) => array[range].to_vec(),
+                };
+                Ok(Cow::Owned(Value::Array(result)))
+            }
+            Self::SteppedRange(range, step) => {
+                let descending = match step.get() < 0 {
+                    true => range.resolve_descending(array.len()),
+                    false => None,
+                };
+                let range = match descending {
+                    Some(range) => range,
+                    None => match self.resolve_range(range, array.len(), context)? {
+                        Some(range) => range,
+                        None => return Ok(null),
+                    },
+                };
+                let result = match array {
+                    Cow::Owned(mut array) => Self::step_items(array.drain(range), *step),
+                    Cow::Borrowed(array) => Self::step_items(array[range].iter().cloned(), *step),
+                };
+                Ok(Cow::Owned(Value::Array(result)))
+            }
+            Self::Index(index) => {
+                let out_of_bounds = context.options().out_of_bounds;
+                let index = match (Self::resolve_index(*index, array.len()), out_of_bounds) {
+                    (Some(index), _) => index,
+                    (None, OutOfBounds::Error) => {
+                        return Err(self.out_of_bounds_error(array.len(), context))
+                    }
+                    (None, OutOfBounds::Null | OutOfBounds::Clamp) => return Ok(null),
+                };
+                match array {
+                    Cow::Owned(mut array) => Ok(Cow::Owned(array.swap_remove(index))),
+                    Cow::Borrowed(array) => Ok(Cow::Borrowed(&array[index])),
+                }
+            }
+        }
+    }
+
+    /// Resolves the range according to the out of bounds behaviour of the context.
+    /// Returns `None` if the output should be `null`.
+    fn resolve_range(
+        &self,
+        range: &IndexingRange,
+        length: usize,
+        context: &Context,
+    ) -> Result<Option<Range<usize>>, Error> {
+        match (range.resolve(length), context.options().out_of_bounds) {
+            (Some(range), _) => Ok(Some(range)),
+            (None, OutOfBounds::Error) => Err(self.out_of_bounds_error(length, context)),
+            (None, OutOfBounds::Null) => Ok(None),
+            (None, OutOfBounds::Clamp) => Ok(Some(range.resolve_clamped(length))),
+        }
+    }
+
+    fn step_items<I>(items: I, step: NonZeroIsize) -> Vec<Value>
+    where
+        I: DoubleEndedIterator<Item = Value>,
+    {
+        let step_size = step.unsigned_abs().get();
+        if step.get() > 0 {
+            items.step_by(step_size).collect()
+        } else {
+            items.rev().step_by(step_size).collect()
+        }
+    }
+
+    fn out_of_bounds_error(&self, length: usize, context: &Context) -> Error {
+        Error::IndexOutOfBounds {
+            indexing_value: self.clone(),
+            length,
+            path: OwnedJsonPath::from(context.path()),
+        }
+    }
+}
+
+impl Display for IndexingValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Range(range) => range.fmt(f),
+            Self::SteppedRange(range, step) => write!(f, "{range}:{step}"),
+            Self::Index(index) => index.fmt(f),
+        }
+    }
+}
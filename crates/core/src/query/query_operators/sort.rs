@@ -0,0 +1,151 @@
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+};
+
+use derive_getters::Getters;
+use derive_more::Constructor;
+use serde_json::{Map, Number, Value};
+
+use super::inspect_or_null;
+use crate::query::{apply::InternalError, context::Context, query_arguments, query_key::QueryKey};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl Display for SortOrder {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SortOrder::Ascending => "asc".fmt(f),
+            SortOrder::Descending => "desc".fmt(f),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Constructor, Getters)]
+pub struct SortKey {
+    key: QueryKey,
+    order: SortOrder,
+}
+
+impl Display for SortKey {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.key().fmt(f)?;
+        match self.order() {
+            SortOrder::Ascending => Ok(()),
+            SortOrder::Descending => write!(f, " {}", self.order()),
+        }
+    }
+}
+
+/// Sorts the array by the given keys, which are compared in order. If there are no keys,
+/// the items are compared by themselves. The sort is stable, so items with the same
+/// keys keep their relative order.
+pub fn sort<'a>(
+    sort_keys: &'a [SortKey],
+    array: Cow<Vec<Value>>,
+    context: &Context<'a>,
+) -> Result<Vec<Value>, InternalError<'a>> {
+    let array_context = context.enter_array();
+    let inspect_keys = |(index, item): (usize, &Value)| {
+        let item_context = array_context.push_index(index);
+        sort_keys
+            .iter()
+            .map(|sort_key| inspect_or_null(sort_key.key(), item, &item_context))
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    let keys = array
+        .iter()
+        .enumerate()
+        .map(inspect_keys)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut keyed_items = keys.into_iter().zip(array.into_owned()).collect::<Vec<_>>();
+    keyed_items.sort_by(|(lhs_keys, lhs_item), (rhs_keys, rhs_item)| {
+        if sort_keys.is_empty() {
+            return cmp_values(lhs_item, rhs_item);
+        }
+        sort_keys
+            .iter()
+            .zip(lhs_keys.iter().zip(rhs_keys))
+            .map(|(sort_key, (lhs, rhs))| match sort_key.order() {
+                SortOrder::Ascending => cmp_values(lhs, rhs),
+                SortOrder::Descending => cmp_values(rhs, lhs),
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+
+    Ok(keyed_items.into_iter().map(|(_, item)| item).collect())
+}
+
+/// Total order between JSON values. Values of different types are ordered by their type, with
+/// `null < bool < number < string < array < object`. Arrays are compared lexicographically, and
+/// objects are compared as lexicographically sorted lists of key-value pairs.
+pub fn cmp_values(lhs: &Value, rhs: &Value) -> Ordering {
+    match (lhs, rhs) {
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Bool(lhs), Value::Bool(rhs)) => lhs.cmp(rhs),
+        (Value::Number(lhs), Value::Number(rhs)) => cmp_numbers(lhs, rhs),
+        (Value::String(lhs), Value::String(rhs)) => lhs.cmp(rhs),
+        (Value::Array(lhs), Value::Array(rhs)) => {
+            cmp_sequences(lhs.iter(), rhs.iter(), |lhs, rhs| cmp_values(lhs, rhs))
+        }
+        (Value::Object(lhs), Value::Object(rhs)) => {
+            cmp_sequences(sorted_entries(lhs), sorted_entries(rhs), |lhs, rhs| {
+                lhs.0.cmp(rhs.0).then_with(|| cmp_values(lhs.1, rhs.1))
+            })
+        }
+        _ => type_rank(lhs).cmp(&type_rank(rhs)),
+    }
+}
+
+fn type_rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::Bool(_) => 1,
+        Value::Number(_) => 2,
+        Value::String(_) => 3,
+        Value::Array(_) => 4,
+        Value::Object(_) => 5,
+    }
+}
+
+/// Numbers are compared by their exact value, as in the arguments, so sorting and the operators
+/// that compare values agree with them
+fn cmp_numbers(lhs: &Number, rhs: &Number) -> Ordering {
+    lhs.partial_cmp(&query_arguments::Number::from(rhs))
+        .expect("JSON numbers are never NaN, so they are always comparable")
+}
+
+fn cmp_sequences<I, F>(lhs: I, rhs: I, cmp: F) -> Ordering
+where
+    I: IntoIterator,
+    F: Fn(&I::Item, &I::Item) -> Ordering,
+{
+    let mut lhs = lhs.into_iter();
+    let mut rhs = rhs.into_iter();
+    loop {
+        match (lhs.next(), rhs.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(lhs), Some(rhs)) => match cmp(&lhs, &rhs) {
+                Ordering::Equal => continue,
+                ordering => return ordering,
+            },
+        }
+    }
+}
+
+fn sorted_entries(object: &Map<String, Value>) -> Vec<(&String, &Value)> {
+    let mut entries = object.iter().collect::<Vec<_>>();
+    entries.sort_by(|lhs, rhs| lhs.0.cmp(rhs.0));
+    entries
+}
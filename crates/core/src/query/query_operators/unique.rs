@@ -0,0 +1,80 @@
+use std::{borrow::Cow, cmp::Ordering, collections::BTreeSet};
+
+use serde_json::Value;
+
+use super::{cmp_values, inspect_or_null};
+use crate::query::{apply::InternalError, context::Context, query_key::QueryKey};
+
+/// Removes the duplicated items of the array, keeping the first occurrence of each one. If there is
+/// a key, items are compared by the value at that key instead of by themselves. Values are
+/// compared as in `cmp_values`, so objects are equal regardless of the order of their keys and
+/// numbers are equal if they have the same numeric value, such as `1` and `1.0`.
+pub fn unique<'a>(
+    unique_key: Option<&'a QueryKey>,
+    array: Cow<Vec<Value>>,
+    context: &Context<'a>,
+) -> Result<Vec<Value>, InternalError<'a>> {
+    let Some(unique_key) = unique_key else {
+        let mut seen = BTreeSet::new();
+        let is_new = array
+            .iter()
+            .map(|item| seen.insert(OrderedValue(item)))
+            .collect::<Vec<_>>();
+        return Ok(retain(array, is_new));
+    };
+
+    let array_context = context.enter_array();
+    let keys = array
+        .iter()
+        .enumerate()
+        .map(|(index, item)| inspect_or_null(unique_key, item, &array_context.push_index(index)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut seen = BTreeSet::new();
+    let is_new = keys
+        .iter()
+        .map(|key| seen.insert(OrderedValue(key)))
+        .collect();
+    Ok(retain(array, is_new))
+}
+
+/// Keeps the new items, only cloning them if the array is borrowed
+fn retain(array: Cow<Vec<Value>>, is_new: Vec<bool>) -> Vec<Value> {
+    match array {
+        Cow::Owned(array) => array
+            .into_iter()
+            .zip(is_new)
+            .filter(|(_, is_new)| *is_new)
+            .map(|(item, _)| item)
+            .collect(),
+        Cow::Borrowed(array) => array
+            .iter()
+            .zip(is_new)
+            .filter(|(_, is_new)| *is_new)
+            .map(|(item, _)| item.clone())
+            .collect(),
+    }
+}
+
+/// Wrapper to store values in ordered collections
+struct OrderedValue<'a>(&'a Value);
+
+impl PartialEq for OrderedValue<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for OrderedValue<'_> {}
+
+impl PartialOrd for OrderedValue<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedValue<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_values(self.0, other.0)
+    }
+}
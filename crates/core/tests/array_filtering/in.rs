@@ -0,0 +1,138 @@
+use crate::fixtures::{ai_models, products};
+use gq_core::query::Query;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+#[rstest]
+fn strings(products: Value) {
+    let query: Query = r#"products(name in ["Product 1", "Product 3"]).name"#
+        .parse()
+        .unwrap();
+    let expected = json!(["Product 1", "Product 3"]);
+
+    let result = query.apply(products).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn numbers(products: Value) {
+    let query: Query = "products(quantity in [4, 5]).name".parse().unwrap();
+    let expected = json!(["Product 2", "Product 3"]);
+
+    let result = query.apply(products).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn empty_list(products: Value) {
+    let query: Query = "products(quantity in []).name".parse().unwrap();
+    let expected = json!([]);
+
+    let result = query.apply(products).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn array_value(ai_models: Value) {
+    let query: Query = r#"models(tags in ["NLP", "Vision"]).name"#.parse().unwrap();
+    let expected = json!(["GPT-4O"]);
+
+    let result = query.apply(ai_models).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn mixed_types() {
+    let value = json!({
+        "items": [
+            { "id": 1, "status": "open" },
+            { "id": 2, "status": 404 },
+            { "id": 3, "status": null },
+            { "id": 4 }
+        ]
+    });
+    let query: Query = r#"items(status in ["open", null]).id"#.parse().unwrap();
+    let expected = json!([1, 3, 4]);
+
+    let result = query
+        .apply_with_diagnostics(value, Default::default())
+        .unwrap();
+
+    assert_eq!(result.value, expected);
+    assert!(result
+        .diagnostics
+        .iter()
+        .all(|diagnostic| diagnostic.kind().name() == "nullDefault"));
+}
+
+#[test]
+fn nested_lists() {
+    let value = json!({
+        "points": [
+            { "id": 1, "position": [0, 0] },
+            { "id": 2, "position": [1, 2] },
+            { "id": 3, "position": [2, 1] }
+        ]
+    });
+    let query: Query = "points(all position in [[1, 2], 0]).id".parse().unwrap();
+    let expected = json!([1]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn list_equality() {
+    let value = json!({
+        "items": [
+            { "id": 1, "tags": ["a", "b"] },
+            { "id": 2, "tags": ["b", "a"] },
+            { "id": 3, "tags": ["a"] }
+        ]
+    });
+    let query: Query = r#"items(tags = ["a", "b"]).id"#.parse().unwrap();
+    let expected = json!([1]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn key_list() {
+    let value = json!({
+        "users": [
+            { "name": "a", "role": "admin", "allowed": ["admin", "owner"] },
+            { "name": "b", "role": "guest", "allowed": ["admin"] }
+        ]
+    });
+    let query: Query = "users(role in allowed).name".parse().unwrap();
+    let expected = json!(["a"]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn in_and_not_fields() {
+    let value = json!({
+        "rooms": [
+            { "in": 2, "not": "a" },
+            { "in": 5, "not": "b" }
+        ]
+    });
+    let query: Query = r#"rooms(in in [1, 2] || not not in ["a", "b"]) { in not }"#
+        .parse()
+        .unwrap();
+    let expected = json!([{ "in": 2, "not": "a" }]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
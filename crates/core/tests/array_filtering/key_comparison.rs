@@ -0,0 +1,100 @@
+use gq_core::query::Query;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::orders;
+
+#[rstest]
+#[case::greater("orders(shipped_at > ordered_at).id", json!([1]))]
+#[case::greater_equal("orders(shipped_at >= ordered_at).id", json!([1, 3]))]
+#[case::less("orders(shipped_at < ordered_at).id", json!([2]))]
+#[case::less_equal("orders(shipped_at <= ordered_at).id", json!([2, 3]))]
+#[case::equal("orders(carrier = preferred).id", json!([1, 3]))]
+#[case::not_equal("orders(carrier != preferred).id", json!([2]))]
+#[case::with_literal(r#"orders(carrier = preferred, carrier = "dhl").id"#, json!([3]))]
+#[case::quoted_literal(r#"orders(carrier = "preferred").id"#, json!([]))]
+fn key_comparison(orders: Value, #[case] query: &str, #[case] expected: Value) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(orders).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn nested_key_comparison() {
+    let value = json!({
+        "accounts": [
+            { "name": "a", "used": 10, "limits": { "quota": 5 } },
+            { "name": "b", "used": 3, "limits": { "quota": 5 } }
+        ]
+    });
+    let query: Query = "accounts(used >= limits.quota).name".parse().unwrap();
+    let expected = json!(["a"]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn root_key_comparison() {
+    let value = json!({ "used": 10, "quota": 5 });
+    let query: Query = "(used >= quota)".parse().unwrap();
+    let expected = json!({ "used": 10, "quota": 5 });
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn key_comparison_with_quantifier() {
+    let value = json!({
+        "students": [
+            { "name": "a", "scores": [6, 8], "pass": 5 },
+            { "name": "b", "scores": [4, 8], "pass": 5 }
+        ]
+    });
+    let query: Query = "students(all scores > pass).name".parse().unwrap();
+    let expected = json!(["a"]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn missing_compared_key() {
+    let value = json!({
+        "items": [
+            { "id": 1, "a": null },
+            { "id": 2, "a": 1, "b": 1 }
+        ]
+    });
+    let query: Query = "items(a = b).id".parse().unwrap();
+    let expected = json!([1, 2]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn incompatible_compared_value() {
+    let value = json!({
+        "items": [
+            { "id": 1, "a": 2, "b": "1" },
+            { "id": 2, "a": 2, "b": 1 }
+        ]
+    });
+    let query: Query = "items(a > b).id".parse().unwrap();
+    let expected = json!([2]);
+
+    let result = query
+        .apply_with_diagnostics(value, Default::default())
+        .unwrap();
+
+    assert_eq!(result.value, expected);
+    assert_eq!(result.diagnostics.len(), 1);
+}
@@ -490,3 +490,29 @@ fn multiple_arguments(products: Value) {
 
     assert_eq!(result, expected);
 }
+
+#[rstest]
+fn ignore_case_flag(products: Value) {
+    let query: Query = r#"products(name ~ "^product 3$"i).name"#.parse().unwrap();
+    let expected = json!(["Product 3"]);
+
+    let result = query.apply(products).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn multi_line_flag() {
+    let value = json!({
+        "logs": [
+            { "id": 1, "message": "started\nERROR: disk full" },
+            { "id": 2, "message": "started\nfinished with no ERROR" }
+        ]
+    });
+    let query: Query = r#"logs(message ~ "^error:"im).id"#.parse().unwrap();
+    let expected = json!([1]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
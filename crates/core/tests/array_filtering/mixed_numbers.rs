@@ -0,0 +1,88 @@
+use gq_core::query::Query;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::items;
+
+#[rstest]
+#[case::float_greater_than_integer("items(value > 10).id", json!([1, 5]))]
+#[case::float_less_than_integer("items(value < 11).id", json!([1, 2, 3, 4]))]
+#[case::integer_greater_than_float("items(value > 9.5).id", json!([1, 2, 5]))]
+#[case::integer_equal_to_float("items(value = 10.0).id", json!([2]))]
+#[case::float_equal_to_integer("items(value = 2).id", json!([4]))]
+#[case::negative_integer("items(value < 0).id", json!([3]))]
+#[case::negative_literal("items(value > -1).id", json!([1, 2, 4, 5]))]
+#[case::negative_float("items(value >= -3.5).id", json!([1, 2, 3, 4, 5]))]
+#[case::not_equal("items(value != 10.0).id", json!([1, 3, 4, 5]))]
+#[case::in_list("items(value in [2, 10.5]).id", json!([1, 4]))]
+fn mixed_numbers(items: Value, #[case] query: &str, #[case] expected: Value) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(items).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn integers_beyond_float_precision() {
+    // 2^53 + 1 cannot be represented as f64, so it would be equal to 2^53 if converted
+    let value = json!({
+        "items": [
+            { "id": 1, "value": 9007199254740993u64 },
+            { "id": 2, "value": 9007199254740992u64 }
+        ]
+    });
+    let query: Query = "items(value > 9007199254740992.0).id".parse().unwrap();
+    let expected = json!([1]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn integers_beyond_i64_range() {
+    let value = json!({
+        "items": [
+            { "id": 1, "value": 18446744073709551615u64 },
+            { "id": 2, "value": -9223372036854775808i64 }
+        ]
+    });
+    let query: Query = "items(value > -9223372036854775808).id".parse().unwrap();
+    let expected = json!([1]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn mixed_key_comparison() {
+    let value = json!({
+        "accounts": [
+            { "name": "a", "used": 5, "quota": 5.5 },
+            { "name": "b", "used": 6, "quota": 5.5 },
+            { "name": "c", "used": 5.0, "quota": 5 }
+        ]
+    });
+    let query: Query = "accounts(used >= quota).name".parse().unwrap();
+    let expected = json!(["b", "c"]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+#[case::sort("values[sort]", json!([9007199254740992.0, 9007199254740993u64]))]
+#[case::min("values[min]", json!(9007199254740992.0))]
+#[case::max("values[max]", json!(9007199254740993u64))]
+#[case::unique("values[unique]", json!([9007199254740993u64, 9007199254740992.0]))]
+fn operators_beyond_float_precision(#[case] query: &str, #[case] expected: Value) {
+    let value = json!({ "values": [9007199254740993u64, 9007199254740992.0] });
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
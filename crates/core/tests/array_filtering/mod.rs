@@ -6,11 +6,21 @@ use serde_json::{json, Value};
 mod equal;
 mod greater;
 mod greater_equal;
+mod r#in;
+mod key_comparison;
 mod less;
 mod less_equal;
 mod r#match;
+mod mixed_numbers;
+mod not;
 mod not_equal;
+mod not_in;
 mod not_match;
+mod object;
+mod or;
+mod quantifier;
+mod self_reference;
+mod string_operation;
 
 #[rstest]
 fn filter_and_accessing(products: Value) {
@@ -0,0 +1,56 @@
+use gq_core::query::Query;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::{products, programming_languages};
+
+#[rstest]
+fn simple_not(programming_languages: Value) {
+    let query: Query = r#"languages(!popular = true).name"#.parse().unwrap();
+    let expected = json!(["Java"]);
+
+    let result = query.apply(programming_languages).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn not_keyword(programming_languages: Value) {
+    let query: Query = r#"languages(not popular = true).name"#.parse().unwrap();
+    let expected = json!(["Java"]);
+
+    let result = query.apply(programming_languages).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn not_group(products: Value) {
+    let query: Query = r#"products(!(price < 10.0 || price > 20.0)).name"#.parse().unwrap();
+    let expected = json!(["Product 2"]);
+
+    let result = query.apply(products).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn double_not(programming_languages: Value) {
+    let query: Query = r#"languages(!!year = 2010).name"#.parse().unwrap();
+    let expected = json!(["Rust"]);
+
+    let result = query.apply(programming_languages).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+// TODO: assert that a warning is logged with the proper message
+#[rstest]
+fn error_is_not_negated(programming_languages: Value) {
+    let query: Query = r#"languages(!name > 1995)"#.parse().unwrap();
+    let expected = json!([]);
+
+    let result = query.apply(programming_languages).unwrap();
+
+    assert_eq!(result, expected);
+}
@@ -0,0 +1,34 @@
+use crate::fixtures::{ai_models, products};
+use gq_core::query::Query;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+#[rstest]
+fn numbers(products: Value) {
+    let query: Query = "products(quantity not in [4, 5]).name".parse().unwrap();
+    let expected = json!(["Product 1"]);
+
+    let result = query.apply(products).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn empty_list(products: Value) {
+    let query: Query = "products(quantity not in []).name".parse().unwrap();
+    let expected = json!(["Product 1", "Product 2", "Product 3"]);
+
+    let result = query.apply(products).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn array_value(ai_models: Value) {
+    let query: Query = r#"models(tags not in ["NLP"]).name"#.parse().unwrap();
+    let expected = json!(["Claude", "LLAMA"]);
+
+    let result = query.apply(ai_models).unwrap();
+
+    assert_eq!(result, expected);
+}
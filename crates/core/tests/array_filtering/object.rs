@@ -0,0 +1,97 @@
+use gq_core::query::{
+    apply::{ApplyOptions, EmptyElements, Error},
+    Query,
+};
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::services;
+
+#[rstest]
+#[case::satisfied("cache(enabled = true)", json!({ "enabled": true, "port": 6379 }))]
+#[case::unsatisfied("metrics(enabled = true)", json!(null))]
+#[case::satisfied_and_accessing("cache(enabled = true).port", json!(6379))]
+#[case::unsatisfied_and_accessing("metrics(enabled = true).port", json!(null))]
+#[case::several_arguments("cache(enabled = true, port > 1024)", json!({ "enabled": true, "port": 6379 }))]
+fn object_arguments(services: Value, #[case] query: &str, #[case] expected: Value) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(services).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn object_arguments_in_children(services: Value) {
+    let query: Query = "{ cache(enabled = true) { port } metrics(enabled = true) { port } }"
+        .parse()
+        .unwrap();
+    let expected = json!({
+        "cache": { "port": 6379 },
+        "metrics": null
+    });
+
+    let result = query.apply(services).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn object_arguments_inside_array(services: Value) {
+    let query: Query = "services { name tls(enabled = true).cert }"
+        .parse()
+        .unwrap();
+    let expected = json!([
+        { "name": "api", "cert": "api.pem" },
+        { "name": "web", "cert": null }
+    ]);
+
+    let result = query.apply(services).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+#[case::accessing("services.tls(enabled = true).cert", json!(["api.pem"]))]
+#[case::object("services.tls(enabled = true)", json!([{ "enabled": true, "cert": "api.pem" }]))]
+#[case::aggregating("services.tls(enabled = true)[count]", json!(1))]
+fn object_arguments_in_mapping(services: Value, #[case] query: &str, #[case] expected: Value) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(services).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn object_arguments_in_mapping_with_null_elements(services: Value) {
+    let query: Query = "services.tls(enabled = true).cert".parse().unwrap();
+    let options = ApplyOptions {
+        empty_elements: EmptyElements::Null,
+        ..Default::default()
+    };
+
+    let output = query.apply_with_diagnostics(services, options).unwrap();
+
+    assert_eq!(output.value, json!(["api.pem"]));
+    assert!(output.diagnostics.is_empty());
+}
+
+#[test]
+fn root_object_arguments() {
+    let value = json!({ "enabled": false });
+    let query: Query = "(enabled = true)".parse().unwrap();
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, json!(null));
+}
+
+#[rstest]
+fn primitive_arguments_fail(services: Value) {
+    let query: Query = "cache.port(enabled = true)".parse().unwrap();
+
+    let result = query.apply(services);
+
+    assert!(matches!(result, Err(Error::NonFiltrableValue(_))));
+}
@@ -0,0 +1,100 @@
+use gq_core::query::Query;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::{products, programming_languages};
+
+#[rstest]
+fn simple_or(programming_languages: Value) {
+    let query: Query = r#"languages(name = "Java" || year > 2000)"#.parse().unwrap();
+    let expected = json!([
+        {
+            "name": "Java",
+            "popular": false,
+            "year": 1995
+        },
+        {
+            "name": "Rust",
+            "popular": true,
+            "year": 2010
+        }
+    ]);
+
+    let result = query.apply(programming_languages).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn or_keyword(programming_languages: Value) {
+    let query: Query = r#"languages(name = "Java" or year > 2000)"#.parse().unwrap();
+    let expected = json!([
+        {
+            "name": "Java",
+            "popular": false,
+            "year": 1995
+        },
+        {
+            "name": "Rust",
+            "popular": true,
+            "year": 2010
+        }
+    ]);
+
+    let result = query.apply(programming_languages).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn or_binds_tighter_than_comma(products: Value) {
+    let query: Query = r#"products(quantity > 4, price < 10.0 || price > 20.0).name"#
+        .parse()
+        .unwrap();
+    let expected = json!(["Product 1"]);
+
+    let result = query.apply(products).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn grouped_conditions(products: Value) {
+    let query: Query = r#"products((quantity > 4, price > 10.0) || name = "Product 3").name"#
+        .parse()
+        .unwrap();
+    let expected = json!(["Product 2", "Product 3"]);
+
+    let result = query.apply(products).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn or_short_circuits_errors(programming_languages: Value) {
+    let query: Query = r#"languages(popular = true || name > 1995).name"#.parse().unwrap();
+    let expected = json!(["JavaScript", "Rust"]);
+
+    let result = query.apply(programming_languages).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn or_and_not_fields() {
+    let value = json!({
+        "items": [
+            { "or": 1, "not": true },
+            { "or": 2, "not": false }
+        ]
+    });
+    let query: Query = "items(or = 1 or not = false) { or not }".parse().unwrap();
+    let expected = json!([
+        { "or": 1, "not": true },
+        { "or": 2, "not": false }
+    ]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
@@ -0,0 +1,79 @@
+use gq_core::query::Query;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::students;
+
+#[rstest]
+#[case::default(r#"students(tags = "x").name"#, json!(["Alice", "Carol", "Dave"]))]
+#[case::any(r#"students(any tags = "x").name"#, json!(["Alice", "Carol", "Dave"]))]
+#[case::all(r#"students(all tags = "x").name"#, json!(["Carol", "Dave"]))]
+#[case::none(r#"students(none tags = "x").name"#, json!(["Bob"]))]
+#[case::default_not_equal(r#"students(tags != "x").name"#, json!(["Bob"]))]
+#[case::any_not_equal(r#"students(any tags != "x").name"#, json!(["Alice", "Bob"]))]
+#[case::all_not_equal(r#"students(all tags != "x").name"#, json!(["Bob"]))]
+#[case::all_match(r#"students(all tags ~ "^[xy]$").name"#, json!(["Alice", "Bob", "Carol", "Dave"]))]
+fn quantified_equality(students: Value, #[case] query: &str, #[case] expected: Value) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(students).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+#[case::any("students(any scores > 5).name", json!(["Alice", "Bob", "Dave"]))]
+#[case::all("students(all scores > 5).name", json!(["Alice", "Carol", "Dave"]))]
+#[case::none("students(none scores > 5).name", json!(["Carol"]))]
+#[case::all_less_equal("students(all scores <= 8).name", json!(["Bob", "Carol", "Dave"]))]
+fn quantified_comparison(students: Value, #[case] query: &str, #[case] expected: Value) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(students).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn negated_quantifier(students: Value) {
+    let query: Query = "students(!all scores > 5).name".parse().unwrap();
+    let expected = json!(["Bob"]);
+
+    let result = query.apply(students).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn incomparable_element_does_not_satisfy() {
+    let value = json!({
+        "items": [
+            { "name": "Item 1", "values": [1, "two"] },
+            { "name": "Item 2", "values": [1, 2] }
+        ]
+    });
+    let query: Query = "items(all values > 0).name".parse().unwrap();
+    let expected = json!(["Item 2"]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn quantifier_fields() {
+    let value = json!({
+        "checks": [
+            { "name": "Check 1", "all": [1, 2], "none": false },
+            { "name": "Check 2", "all": [3], "none": true }
+        ]
+    });
+    let query: Query = "checks(all all < 3, none = false) { name none }"
+        .parse()
+        .unwrap();
+    let expected = json!([{ "name": "Check 1", "none": false }]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
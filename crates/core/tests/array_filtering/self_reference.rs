@@ -0,0 +1,64 @@
+use gq_core::query::Query;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::servers;
+
+#[rstest]
+#[case::string(r#"servers[0].tags(@ ~ "^prod")"#, json!(["prod-eu", "prod-us"]))]
+#[case::number("servers[0].ports(@ > 100)", json!([443, 8080]))]
+#[case::negated(r#"servers[0].tags(!@ ~ "^prod")"#, json!(["http"]))]
+#[case::inside_array(r#"servers.tags(@ ~ "^prod")"#, json!([["prod-eu", "prod-us"]]))]
+fn self_reference(servers: Value, #[case] query: &str, #[case] expected: Value) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(servers).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn self_reference_in_children(servers: Value) {
+    let query: Query = r#"servers { name tags(@ ~ "^prod") }"#.parse().unwrap();
+    let expected = json!([
+        { "name": "api", "tags": ["prod-eu", "prod-us"] },
+        { "name": "db", "tags": [] }
+    ]);
+
+    let result = query.apply(servers).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn root_self_reference() {
+    let value = json!([1, 2, 3]);
+    let query: Query = "(@ >= 2)".parse().unwrap();
+    let expected = json!([2, 3]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn self_reference_with_nested_arrays() {
+    let value = json!({ "matrix": [[1, 2], [3], [0]] });
+    let query: Query = "matrix(@ > 1)".parse().unwrap();
+    let expected = json!([[2], [3]]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn self_reference_with_incomparable_elements() {
+    let value = json!({ "values": [1, "two", 3, null] });
+    let query: Query = "values(@ > 1)".parse().unwrap();
+    let expected = json!([3]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
@@ -0,0 +1,63 @@
+use crate::fixtures::{hosts, programming_languages};
+use gq_core::query::Query;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+#[rstest]
+#[case::starts_with(r#"hosts(name ^= "api.").name"#, json!(["api.example.com"]))]
+#[case::starts_with_ignore_case(
+    r#"hosts(name ^= "api."i).name"#,
+    json!(["api.example.com", "API.staging.example.org"])
+)]
+#[case::ends_with(r#"hosts(name $= ".example.com").name"#, json!(["api.example.com"]))]
+#[case::ends_with_ignore_case(r#"hosts(name $= ".ORG"i).name"#, json!(["API.staging.example.org"]))]
+#[case::contains(r#"hosts(name *= "staging").name"#, json!(["API.staging.example.org"]))]
+#[case::contains_ignore_case(
+    r#"hosts(name *= "API"i).name"#,
+    json!(["api.example.com", "API.staging.example.org", "apixexample.com"])
+)]
+#[case::equal_ignore_case(r#"hosts(name = "API.EXAMPLE.COM"i).name"#, json!(["api.example.com"]))]
+#[case::not_equal_ignore_case(
+    r#"hosts(name != "API.EXAMPLE.COM"i).name"#,
+    json!(["API.staging.example.org", "apixexample.com"])
+)]
+#[case::array_value(r#"hosts(tags ^= "eu"i).name"#, json!(["api.example.com"]))]
+#[case::negated(r#"hosts(!name ^= "api").name"#, json!(["API.staging.example.org"]))]
+fn string_operation(hosts: Value, #[case] query: &str, #[case] expected: Value) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(hosts).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+#[case::same_letters(r#"streets(name = "HAUPTSTRAßE"i).name"#, json!(["Hauptstraße"]))]
+#[case::lowercase_only(r#"streets(name = "HAUPTSTRASSE"i).name"#, json!([]))]
+fn ignore_case_lowercases(#[case] query: &str, #[case] expected: Value) {
+    let value = json!({ "streets": [{ "name": "Hauptstraße" }] });
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn non_string_value(programming_languages: Value) {
+    let query: Query = r#"languages(year ^= "20").name"#.parse().unwrap();
+    let expected = json!([]);
+
+    let result = query
+        .apply_with_diagnostics(programming_languages, Default::default())
+        .unwrap();
+
+    assert_eq!(result.value, expected);
+    assert!(result
+        .diagnostics
+        .iter()
+        .all(|diagnostic| diagnostic.kind().name() == "argumentError"));
+    assert!(result.diagnostics[0]
+        .message()
+        .contains("operation '^=' is not compatible with value type 'number'"));
+}
@@ -0,0 +1,111 @@
+use gq_core::query::{apply::ApplyOptions, diagnostic::DiagnosticKind, Query};
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::customers;
+
+#[rstest]
+fn no_diagnostics(customers: Value) {
+    let query: Query = "customers.name".parse().unwrap();
+
+    let output = query
+        .apply_with_diagnostics(customers, ApplyOptions::default())
+        .unwrap();
+
+    assert_eq!(output.value, json!(["Alice", "Bob", "Carol"]));
+    assert!(output.diagnostics.is_empty());
+}
+
+#[rstest]
+fn discarded_element(customers: Value) {
+    let query: Query = "customers.email".parse().unwrap();
+
+    let output = query
+        .apply_with_diagnostics(customers, ApplyOptions::default())
+        .unwrap();
+
+    assert_eq!(output.value, json!(["alice@example.com", null]));
+    let [diagnostic] = output.diagnostics.as_slice() else {
+        panic!("expected a single diagnostic, got {:?}", output.diagnostics);
+    };
+    assert_eq!(*diagnostic.kind(), DiagnosticKind::DiscardedElement);
+    assert_eq!(diagnostic.path().to_string(), ".customers");
+    assert_eq!(
+        diagnostic.message(),
+        "key '.customers[1].email' not found while indexing inside array '.customers'"
+    );
+    assert_eq!(diagnostic.span(), &Some(10..15));
+}
+
+#[rstest]
+fn discarded_child(customers: Value) {
+    let query: Query = "customers { name email }".parse().unwrap();
+
+    let output = query
+        .apply_with_diagnostics(customers, ApplyOptions::default())
+        .unwrap();
+
+    let [diagnostic] = output.diagnostics.as_slice() else {
+        panic!("expected a single diagnostic, got {:?}", output.diagnostics);
+    };
+    assert_eq!(*diagnostic.kind(), DiagnosticKind::DiscardedElement);
+    assert_eq!(diagnostic.span(), &Some(17..22));
+}
+
+#[rstest]
+fn argument_error(customers: Value) {
+    let query: Query = "customers(age > 30).name".parse().unwrap();
+
+    let output = query
+        .apply_with_diagnostics(customers, ApplyOptions::default())
+        .unwrap();
+
+    assert_eq!(output.value, json!(["Alice"]));
+    let [diagnostic] = output.diagnostics.as_slice() else {
+        panic!("expected a single diagnostic, got {:?}", output.diagnostics);
+    };
+    assert_eq!(*diagnostic.kind(), DiagnosticKind::ArgumentError);
+    assert_eq!(diagnostic.path().to_string(), ".customers[1]");
+}
+
+#[rstest]
+#[case::optional_key("customers.email?")]
+#[case::argument_key("customers(email = null).name")]
+#[case::operator_key("customers[sort(email)].name")]
+fn null_default(customers: Value, #[case] query: &str) {
+    let query: Query = query.parse().unwrap();
+
+    let output = query
+        .apply_with_diagnostics(customers, ApplyOptions::default())
+        .unwrap();
+
+    let [diagnostic] = output.diagnostics.as_slice() else {
+        panic!("expected a single diagnostic, got {:?}", output.diagnostics);
+    };
+    assert_eq!(*diagnostic.kind(), DiagnosticKind::NullDefault);
+    assert_eq!(diagnostic.path().to_string(), ".customers[1].email");
+}
+
+#[rstest]
+fn merged_group() {
+    let query: Query = "items[groupBy(id)]".parse().unwrap();
+    let value = json!({ "items": [{ "id": "1" }, { "id": 1 }, { "id": "1" }] });
+
+    let output = query
+        .apply_with_diagnostics(value, ApplyOptions::default())
+        .unwrap();
+
+    assert_eq!(
+        output.value,
+        json!({ "1": [{ "id": "1" }, { "id": 1 }, { "id": "1" }] })
+    );
+    let [diagnostic] = output.diagnostics.as_slice() else {
+        panic!("expected a single diagnostic, got {:?}", output.diagnostics);
+    };
+    assert_eq!(*diagnostic.kind(), DiagnosticKind::MergedGroup);
+    assert_eq!(diagnostic.path().to_string(), ".items[1]");
+    assert_eq!(
+        diagnostic.message(),
+        "value 1 is merged with value \"1\" in group '1'"
+    );
+}
@@ -0,0 +1,85 @@
+use gq_core::query::{
+    apply::{ApplyOptions, EmptyElements},
+    Query,
+};
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::customers;
+
+#[rstest]
+#[case::remove(
+    EmptyElements::Remove,
+    json!([{ "email": "alice@example.com" }, { "email": null }])
+)]
+#[case::keep(
+    EmptyElements::Keep,
+    json!([{ "email": "alice@example.com" }, {}, { "email": null }])
+)]
+#[case::null(
+    EmptyElements::Null,
+    json!([{ "email": "alice@example.com" }, null, { "email": null }])
+)]
+fn empty_objects(customers: Value, #[case] empty_elements: EmptyElements, #[case] expected: Value) {
+    let query: Query = "customers { email }".parse().unwrap();
+    let options = ApplyOptions {
+        empty_elements,
+        ..Default::default()
+    };
+
+    let result = query.apply_with_options(customers, options).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+#[case::remove(EmptyElements::Remove, json!([[1]]))]
+#[case::keep(EmptyElements::Keep, json!([[1], [], []]))]
+#[case::null(EmptyElements::Null, json!([[1], [null], null]))]
+fn empty_arrays(customers: Value, #[case] empty_elements: EmptyElements, #[case] expected: Value) {
+    let query: Query = "customers.orders.id".parse().unwrap();
+    let options = ApplyOptions {
+        empty_elements,
+        ..Default::default()
+    };
+
+    let result = query.apply_with_options(customers, options).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+#[case::remove(EmptyElements::Remove, json!(["Vigo"]))]
+#[case::keep(EmptyElements::Keep, json!(["Vigo"]))]
+#[case::null(EmptyElements::Null, json!(["Vigo", null, null]))]
+fn discarded_elements(
+    customers: Value,
+    #[case] empty_elements: EmptyElements,
+    #[case] expected: Value,
+) {
+    let query: Query = "customers.address.city".parse().unwrap();
+    let options = ApplyOptions {
+        empty_elements,
+        ..Default::default()
+    };
+
+    let result = query.apply_with_options(customers, options).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn null_keeps_indexes_aligned(customers: Value) {
+    let query: Query = "customers { email }".parse().unwrap();
+    let options = ApplyOptions {
+        empty_elements: EmptyElements::Null,
+        ..Default::default()
+    };
+
+    let result = query
+        .apply_with_options(customers.clone(), options)
+        .unwrap();
+
+    let input_length = customers["customers"].as_array().unwrap().len();
+    assert_eq!(result.as_array().unwrap().len(), input_length);
+}
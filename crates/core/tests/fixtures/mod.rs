@@ -78,3 +78,164 @@ pub fn ai_models() -> Value {
         }
     )
 }
+
+#[fixture]
+pub fn teams() -> Value {
+    json!({
+        "teams": [
+            {
+                "name": "Core",
+                "members": [{ "name": "Alice" }, { "name": "Bob" }]
+            },
+            {
+                "name": "Web",
+                "members": [{ "name": "Carol" }]
+            },
+            {
+                "name": "Docs",
+                "members": []
+            }
+        ]
+    })
+}
+
+#[fixture]
+pub fn users() -> Value {
+    json!({
+        "users": {
+            "u1": { "name": "Alice", "age": 31, "email": "alice@example.com" },
+            "u2": { "name": "Bob", "age": 25 },
+            "u3": { "name": "Carol", "age": 42, "email": "carol@example.com" }
+        }
+    })
+}
+
+#[fixture]
+pub fn deployment() -> Value {
+    json!({
+        "kind": "Deployment",
+        "metadata": { "name": "web" },
+        "spec": {
+            "template": {
+                "metadata": { "name": "web-pod" },
+                "spec": {
+                    "initContainers": [
+                        { "name": "migrations", "image": "migrate:1.0" }
+                    ],
+                    "containers": [
+                        { "name": "app", "image": "web:2.1", "ports": [{ "port": 80 }] },
+                        { "name": "sidecar", "image": "proxy:0.9" }
+                    ]
+                }
+            }
+        }
+    })
+}
+
+#[fixture]
+pub fn openapi() -> Value {
+    json!({
+        "openapi": "3.0.0",
+        "x-api-id": "petstore",
+        "paths": {
+            "/pets": {
+                "get": { "summary": "List pets", "x-rate-limit": 100 },
+                "post": { "summary": "Create a pet", "x-internal": true },
+                "x-owner": "pets-team"
+            },
+            "/users": {
+                "get": { "summary": "List users" }
+            }
+        }
+    })
+}
+
+#[fixture]
+pub fn customers() -> Value {
+    json!({
+        "customers": [
+            {
+                "name": "Alice",
+                "age": 31,
+                "address": { "city": "Vigo" },
+                "email": "alice@example.com",
+                "orders": [{ "id": 1 }]
+            },
+            { "name": "Bob", "age": "unknown", "address": null, "orders": [{ "code": "A" }] },
+            { "name": "Carol", "age": 25, "email": null, "orders": [] }
+        ],
+        "regions": {
+            "north": { "sales": 10 },
+            "south": { "returns": 2 }
+        }
+    })
+}
+
+#[fixture]
+pub fn students() -> Value {
+    json!({
+        "students": [
+            { "name": "Alice", "tags": ["x", "y"], "scores": [7, 9] },
+            { "name": "Bob", "tags": ["y"], "scores": [4, 8] },
+            { "name": "Carol", "tags": ["x"], "scores": [] },
+            { "name": "Dave", "tags": "x", "scores": 6 }
+        ]
+    })
+}
+
+#[fixture]
+pub fn servers() -> Value {
+    json!({
+        "servers": [
+            { "name": "api", "tags": ["prod-eu", "http", "prod-us"], "ports": [80, 443, 8080] },
+            { "name": "db", "tags": ["dev"], "ports": [5432] }
+        ]
+    })
+}
+
+#[fixture]
+pub fn services() -> Value {
+    json!({
+        "cache": { "enabled": true, "port": 6379 },
+        "metrics": { "enabled": false, "port": 9090 },
+        "services": [
+            { "name": "api", "tls": { "enabled": true, "cert": "api.pem" } },
+            { "name": "web", "tls": { "enabled": false } }
+        ]
+    })
+}
+
+#[fixture]
+pub fn orders() -> Value {
+    json!({
+        "orders": [
+            { "id": 1, "ordered_at": 100, "shipped_at": 150, "carrier": "ups", "preferred": "ups" },
+            { "id": 2, "ordered_at": 200, "shipped_at": 180, "carrier": "dhl", "preferred": "ups" },
+            { "id": 3, "ordered_at": 300, "shipped_at": 300, "carrier": "dhl", "preferred": "dhl" }
+        ]
+    })
+}
+
+#[fixture]
+pub fn hosts() -> Value {
+    json!({
+        "hosts": [
+            { "name": "api.example.com", "tags": ["prod", "EU"] },
+            { "name": "API.staging.example.org", "tags": ["staging"] },
+            { "name": "apixexample.com", "tags": [] }
+        ]
+    })
+}
+
+#[fixture]
+pub fn items() -> Value {
+    json!({
+        "items": [
+            { "id": 1, "value": 10.5 },
+            { "id": 2, "value": 10 },
+            { "id": 3, "value": -3 },
+            { "id": 4, "value": 2.0 },
+            { "id": 5, "value": 18446744073709551615u64 }
+        ]
+    })
+}
@@ -1,7 +1,11 @@
 // TODO: rename this to `query_arguments`?
 mod array_filtering;
+mod diagnostics;
+mod empty_elements;
 mod field_accessing;
 mod field_aliasing;
 mod fixtures;
+mod key_selection;
 mod operator;
 mod other;
+mod strict;
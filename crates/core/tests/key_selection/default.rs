@@ -0,0 +1,76 @@
+use gq_core::query::Query;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::{ai_models, customers};
+
+#[rstest]
+#[case::string("{ id version ?? \"1.0\" }", json!({ "id": "AI-Models", "version": "1.0" }))]
+#[case::number("{ id retries ?? 3 }", json!({ "id": "AI-Models", "retries": 3 }))]
+#[case::negative_number("{ id offset ?? -1 }", json!({ "id": "AI-Models", "offset": -1 }))]
+#[case::bool("{ id active ?? true }", json!({ "id": "AI-Models", "active": true }))]
+#[case::null("{ id version ?? null }", json!({ "id": "AI-Models", "version": null }))]
+#[case::with_alias("{ id version: v ?? \"1.0\" }", json!({ "id": "AI-Models", "v": "1.0" }))]
+fn default_missing_key(ai_models: Value, #[case] query: &str, #[case] expected: Value) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(ai_models).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn default_existing_key(ai_models: Value) {
+    let query: Query = "{ id ?? \"unknown\" }".parse().unwrap();
+    let expected = json!({ "id": "AI-Models" });
+
+    let result = query.apply(ai_models).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn default_missing_or_null_key_inside_array(customers: Value) {
+    let query: Query = "customers { name email ?? \"none\" }".parse().unwrap();
+    let expected = json!([
+        { "name": "Alice", "email": "alice@example.com" },
+        { "name": "Bob", "email": "none" },
+        { "name": "Carol", "email": "none" }
+    ]);
+
+    let result = query.apply(customers).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn default_skips_children(customers: Value) {
+    let query: Query = "customers { name address ?? \"unknown\" { city } }"
+        .parse()
+        .unwrap();
+    let expected = json!([
+        { "name": "Alice", "address": { "city": "Vigo" } },
+        { "name": "Bob", "address": "unknown" },
+        { "name": "Carol", "address": "unknown" }
+    ]);
+
+    let result = query.apply(customers).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn default_after_optional_key(customers: Value) {
+    let query: Query = "customers { name address?.city ?? \"unknown\" }"
+        .parse()
+        .unwrap();
+    let expected = json!([
+        { "name": "Alice", "city": "Vigo" },
+        { "name": "Bob", "city": "unknown" },
+        { "name": "Carol", "city": "unknown" }
+    ]);
+
+    let result = query.apply(customers).unwrap();
+
+    assert_eq!(result, expected);
+}
@@ -0,0 +1,86 @@
+use gq_core::query::Query;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::{ai_models, deployment};
+
+#[rstest]
+fn descendants(deployment: Value) {
+    let query: Query = "**.image".parse().unwrap();
+    let expected = json!(["migrate:1.0", "web:2.1", "proxy:0.9"]);
+
+    let result = query.apply(deployment).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn descendants_include_value_itself(deployment: Value) {
+    let query: Query = "**.metadata.name".parse().unwrap();
+    let expected = json!(["web", "web-pod"]);
+
+    let result = query.apply(deployment).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn descendants_after_key(deployment: Value) {
+    let query: Query = "spec.**.containers.name".parse().unwrap();
+    let expected = json!([["app", "sidecar"]]);
+
+    let result = query.apply(deployment).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn descendants_with_arguments(deployment: Value) {
+    let query: Query = r#"**.containers(name = "app").ports.port"#.parse().unwrap();
+    let expected = json!([[[80]]]);
+
+    let result = query.apply(deployment).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn descendants_with_operators(deployment: Value) {
+    let query: Query = "**.name[count]".parse().unwrap();
+
+    let result = query.apply(deployment).unwrap();
+
+    assert_eq!(result, json!(5));
+}
+
+#[rstest]
+fn descendants_with_children(deployment: Value) {
+    let query: Query = "{ kind **.metadata { name } }".parse().unwrap();
+    let expected = json!({
+        "kind": "Deployment",
+        "metadata": [{ "name": "web" }, { "name": "web-pod" }]
+    });
+
+    let result = query.apply(deployment).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn descendants_in_array(ai_models: Value) {
+    let query: Query = "models.**.score".parse().unwrap();
+    let expected = json!([71.49, null, 88.7]);
+
+    let result = query.apply(ai_models).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn descendants_not_found(deployment: Value) {
+    let query: Query = "**.replicas".parse().unwrap();
+
+    let result = query.apply(deployment).unwrap();
+
+    assert_eq!(result, json!([]));
+}
@@ -0,0 +1,5 @@
+mod default;
+mod descendants;
+mod optional;
+mod pattern;
+mod wildcard;
@@ -0,0 +1,87 @@
+use gq_core::query::{apply::Error, Query};
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::{ai_models, customers};
+
+#[rstest]
+fn missing_key_fails(ai_models: Value) {
+    let query: Query = "{ id version }".parse().unwrap();
+
+    let result = query.apply(ai_models);
+
+    assert!(matches!(result, Err(Error::KeyNotFound(_))));
+}
+
+#[rstest]
+fn optional_missing_key(ai_models: Value) {
+    let query: Query = "{ id version? }".parse().unwrap();
+    let expected = json!({ "id": "AI-Models", "version": null });
+
+    let result = query.apply(ai_models).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn optional_existing_key(ai_models: Value) {
+    let query: Query = "id?".parse().unwrap();
+
+    let result = query.apply(ai_models).unwrap();
+
+    assert_eq!(result, json!("AI-Models"));
+}
+
+#[rstest]
+fn optional_key_inside_array(customers: Value) {
+    let query: Query = "customers.email?".parse().unwrap();
+    let expected = json!(["alice@example.com", null, null]);
+
+    let result = query.apply(customers).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn optional_key_short_circuits(customers: Value) {
+    let query: Query = "customers.address?.city".parse().unwrap();
+    let expected = json!(["Vigo", null, null]);
+
+    let result = query.apply(customers).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn optional_key_of_null(customers: Value) {
+    let query: Query = "customers.address.city?".parse().unwrap();
+    let expected = json!(["Vigo", null]);
+
+    let result = query.apply(customers).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn optional_key_with_children(customers: Value) {
+    let query: Query = "customers { name address? { city } }".parse().unwrap();
+    let expected = json!([
+        { "name": "Alice", "address": { "city": "Vigo" } },
+        { "name": "Bob", "address": null },
+        { "name": "Carol", "address": null }
+    ]);
+
+    let result = query.apply(customers).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn optional_key_with_alias(ai_models: Value) {
+    let query: Query = "{ license?: licenseName }".parse().unwrap();
+    let expected = json!({ "licenseName": null });
+
+    let result = query.apply(ai_models).unwrap();
+
+    assert_eq!(result, expected);
+}
@@ -0,0 +1,83 @@
+use gq_core::query::Query;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::openapi;
+
+#[rstest]
+fn pattern(openapi: Value) {
+    let query: Query = r#"~"^x-""#.parse().unwrap();
+    let expected = json!({ "x-api-id": "petstore" });
+
+    let result = query.apply(openapi).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn nested_pattern(openapi: Value) {
+    let query: Query = r#"paths.*.*.~"^x-""#.parse().unwrap();
+    let expected = json!({
+        "/pets": {
+            "get": { "x-rate-limit": 100 },
+            "post": { "x-internal": true }
+        },
+        "/users": {
+            "get": {}
+        }
+    });
+
+    let result = query.apply(openapi).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn pattern_with_children(openapi: Value) {
+    let query: Query = r#"paths."/pets".~"^(get|post)$" { summary }"#.parse().unwrap();
+    let expected = json!({
+        "get": { "summary": "List pets" },
+        "post": { "summary": "Create a pet" }
+    });
+
+    let result = query.apply(openapi).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn pattern_in_child_query(openapi: Value) {
+    let query: Query = r#"{
+        openapi
+        ~"^x-": extensions
+    }"#
+    .parse()
+    .unwrap();
+    let expected = json!({
+        "openapi": "3.0.0",
+        "extensions": { "x-api-id": "petstore" }
+    });
+
+    let result = query.apply(openapi).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn pattern_without_matches(openapi: Value) {
+    let query: Query = r#"paths.~"^/orders""#.parse().unwrap();
+
+    let result = query.apply(openapi).unwrap();
+
+    assert_eq!(result, json!({}));
+}
+
+#[rstest]
+fn pattern_after_descendants(openapi: Value) {
+    let query: Query = r#"**.~"^x-rate""#.parse().unwrap();
+    let expected = json!([{ "x-rate-limit": 100 }]);
+
+    let result = query.apply(openapi).unwrap();
+
+    assert_eq!(result, expected);
+}
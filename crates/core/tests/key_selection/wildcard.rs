@@ -0,0 +1,175 @@
+use gq_core::query::Query;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::users;
+
+#[rstest]
+fn wildcard_keeps_keys(users: Value) {
+    let query: Query = "users.*.name".parse().unwrap();
+    let expected = json!({ "u1": "Alice", "u2": "Bob", "u3": "Carol" });
+
+    let result = query.apply(users).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn wildcard_with_arguments(users: Value) {
+    let query: Query = "users.*(age > 30).name".parse().unwrap();
+    let expected = json!({ "u1": "Alice", "u3": "Carol" });
+
+    let result = query.apply(users).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn wildcard_with_children(users: Value) {
+    let query: Query = "users.* { name age }".parse().unwrap();
+    let expected = json!({
+        "u1": { "name": "Alice", "age": 31 },
+        "u2": { "name": "Bob", "age": 25 },
+        "u3": { "name": "Carol", "age": 42 }
+    });
+
+    let result = query.apply(users).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn wildcard_in_child_query(users: Value) {
+    let query: Query = "{ users.*(age < 40) { name } }".parse().unwrap();
+    let expected = json!({
+        "users": {
+            "u1": { "name": "Alice" },
+            "u2": { "name": "Bob" }
+        }
+    });
+
+    let result = query.apply(users).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn wildcard_skips_missing_keys(users: Value) {
+    let query: Query = "users.*.email".parse().unwrap();
+    let expected = json!({ "u1": "alice@example.com", "u3": "carol@example.com" });
+
+    let result = query.apply(users).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn root_wildcard() {
+    let value = json!({
+        "a": { "n": 1 },
+        "b": { "n": 2 },
+        "c": { "n": 3 }
+    });
+    let query: Query = "*(n > 1).n".parse().unwrap();
+    let expected = json!({ "b": 2, "c": 3 });
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn nested_wildcards() {
+    let value = json!({
+        "regions": {
+            "north": { "s1": { "sales": 10 }, "s2": { "sales": 20 } },
+            "south": { "s3": { "sales": 5 } }
+        }
+    });
+    let query: Query = "regions.*.* { sales }".parse().unwrap();
+    let expected = json!({
+        "north": { "s1": { "sales": 10 }, "s2": { "sales": 20 } },
+        "south": { "s3": { "sales": 5 } }
+    });
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn wildcard_inside_array() {
+    let value = json!({
+        "teams": [
+            { "leads": { "backend": { "name": "Alice" }, "frontend": { "name": "Bob" } } },
+            { "leads": { "backend": { "name": "Carol" } } }
+        ]
+    });
+    let query: Query = "teams.leads.* { name }".parse().unwrap();
+    let expected = json!([
+        { "backend": { "name": "Alice" }, "frontend": { "name": "Bob" } },
+        { "backend": { "name": "Carol" } }
+    ]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn wildcard_values_aggregation_in_children() {
+    let value = json!({
+        "users": {
+            "u1": { "orders": [{ "total": 10 }, { "total": 5 }] },
+            "u2": { "orders": [{ "total": 7 }] }
+        }
+    });
+    let query: Query = "users.* { orders.total[sum]: total }".parse().unwrap();
+    let expected = json!({ "u1": { "total": 15 }, "u2": { "total": 7 } });
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+#[case::count("users.*[count]", json!(3))]
+#[case::count_filtered("users.*(age > 30)[count]", json!(2))]
+#[case::sum("users.*.age[sum]", json!(98))]
+#[case::sort("users.*[sort(age)].name", json!(["Bob", "Alice", "Carol"]))]
+#[case::indexing("users.*[0].name", json!("Alice"))]
+#[case::unique("users.*.email?[unique]", json!(["alice@example.com", null, "carol@example.com"]))]
+fn wildcard_operators_collect_values(users: Value, #[case] query: &str, #[case] expected: Value) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(users).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn wildcard_values_group_by(users: Value) {
+    let query: Query = "users.*[groupBy(email?)] { name }".parse().unwrap();
+    let expected = json!({
+        "alice@example.com": [{ "name": "Alice" }],
+        "null": [{ "name": "Bob" }],
+        "carol@example.com": [{ "name": "Carol" }]
+    });
+
+    let result = query.apply(users).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn wildcard_child_output_key(users: Value) {
+    let query: Query = "{ users.*.name users.*: all }".parse().unwrap();
+
+    let result = query.apply(users.clone()).unwrap();
+
+    assert_eq!(
+        result["name"],
+        json!({ "u1": "Alice", "u2": "Bob", "u3": "Carol" })
+    );
+    assert_eq!(result["all"], users["users"]);
+}
@@ -0,0 +1,149 @@
+use gq_core::query::{apply::Error, query_operators, Query};
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::{ai_models, products};
+
+#[rstest]
+#[case::count("products[count]", json!(3))]
+#[case::sum("products.quantity[sum]", json!(17))]
+#[case::avg("products.quantity[avg]", json!(17.0 / 3.0))]
+#[case::min("products.price[min]", json!(9.95))]
+#[case::max("products.price[max]", json!(24.95))]
+#[case::filtered_sum("products(quantity > 4).quantity[sum]", json!(13))]
+#[case::filtered_count("products(price < 20.0)[count]", json!(2))]
+#[case::after_sort("products[sort(price)].quantity[sum]", json!(17))]
+fn aggregation(products: Value, #[case] query: &str, #[case] expected: Value) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(products).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+#[case::count("products(quantity > 100)[count]", json!(0))]
+#[case::sum("products(quantity > 100).quantity[sum]", json!(0))]
+#[case::avg("products(quantity > 100).quantity[avg]", json!(null))]
+#[case::min("products(quantity > 100).quantity[min]", json!(null))]
+#[case::max("products(quantity > 100).quantity[max]", json!(null))]
+fn empty_array_aggregation(products: Value, #[case] query: &str, #[case] expected: Value) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(products).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn aggregations_in_children(products: Value) {
+    let query: Query = r#"{
+        products[count]: count
+        products.quantity[sum]: quantity
+        products.price[max]: maxPrice
+    }"#
+    .parse()
+    .unwrap();
+    let expected = json!({
+        "count": 3,
+        "quantity": 17,
+        "maxPrice": 24.95
+    });
+
+    let result = query.apply(products).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn aggregation_per_item() {
+    let value = json!({
+        "orders": [
+            { "id": 1, "items": [{ "price": 2 }, { "price": 3 }] },
+            { "id": 2, "items": [{ "price": 10 }] }
+        ]
+    });
+    let query: Query = r#"orders {
+        id
+        items.price[sum]: total
+    }"#
+    .parse()
+    .unwrap();
+    let expected = json!([
+        { "id": 1, "total": 5 },
+        { "id": 2, "total": 10 }
+    ]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn aggregation_skips_missing_keys(ai_models: Value) {
+    let query: Query = "models.tags[count]".parse().unwrap();
+
+    let result = query.apply(ai_models).unwrap();
+
+    assert_eq!(result, json!(2));
+}
+
+#[rstest]
+#[case::min("[min]", json!(-2.5))]
+#[case::max("[max]", json!(10))]
+fn mixed_numbers_extreme(#[case] query: &str, #[case] expected: Value) {
+    let value = json!([3, -2.5, 10, 7.25]);
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+#[case::bigger_than_i64(json!([i64::MAX, 1]), json!(i64::MAX as u64 + 1))]
+#[case::back_into_i64(json!([u64::MAX, i64::MIN, i64::MIN]), json!(-1))]
+#[case::bigger_than_u64(json!([u64::MAX, 1]), json!(u64::MAX as f64))]
+fn big_integer_sum(#[case] value: Value, #[case] expected: Value) {
+    let query: Query = "[sum]".parse().unwrap();
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+#[case::non_array("id[count]", "string")]
+#[case::non_numeric_sum("models.name[sum]", "string")]
+#[case::null_avg("models.score[avg]", "null")]
+#[case::string_min("models.name[min]", "string")]
+#[case::null_max("models.score[max]", "null")]
+fn unsupported_type(ai_models: Value, #[case] query: &str, #[case] expected_type: &str) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(ai_models);
+
+    assert!(matches!(
+        result,
+        Err(Error::QueryOperatorError(
+            query_operators::Error::UnsupportedType { value_type, .. }
+        )) if value_type == expected_type
+    ));
+}
+
+#[rstest]
+#[case::min("[min]", "string")]
+#[case::max("[max]", "string")]
+fn mixed_types_extreme(#[case] query: &str, #[case] expected_type: &str) {
+    let value = json!([1, "x", null]);
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(value);
+
+    assert!(matches!(
+        result,
+        Err(Error::QueryOperatorError(
+            query_operators::Error::UnsupportedType { value_type, .. }
+        )) if value_type == expected_type
+    ));
+}
@@ -0,0 +1,62 @@
+use gq_core::query::{apply::Error, query_operators, Query};
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::{ai_models, teams};
+
+#[rstest]
+fn flatten_inside_array(teams: Value) {
+    let query: Query = "teams.members[flatten].name".parse().unwrap();
+    let expected = json!(["Alice", "Bob", "Carol"]);
+
+    let result = query.apply(teams).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn flatten_then_count(teams: Value) {
+    let query: Query = "teams.members[flatten][count]".parse().unwrap();
+
+    let result = query.apply(teams).unwrap();
+
+    assert_eq!(result, json!(3));
+}
+
+#[rstest]
+fn flatten_then_unique(ai_models: Value) {
+    let query: Query = "models.tags[flatten][unique]".parse().unwrap();
+    let expected = json!(["NLP", "Text Generation", "Open Source"]);
+
+    let result = query.apply(ai_models).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+#[case::default_depth("values[flatten]", json!([1, 2, [3, [4]], 5]))]
+#[case::zero_depth("values[flatten(0)]", json!([1, [2, [3, [4]]], 5]))]
+#[case::depth("values[flatten(2)]", json!([1, 2, 3, [4], 5]))]
+#[case::depth_bigger_than_nesting("values[flatten(10)]", json!([1, 2, 3, 4, 5]))]
+fn flatten_depth(#[case] query: &str, #[case] expected: Value) {
+    let value = json!({ "values": [1, [2, [3, [4]]], 5] });
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn unsupported_type(teams: Value) {
+    let query: Query = "teams[count][flatten]".parse().unwrap();
+
+    let result = query.apply(teams);
+
+    assert!(matches!(
+        result,
+        Err(Error::QueryOperatorError(
+            query_operators::Error::UnsupportedType { .. }
+        ))
+    ));
+}
@@ -0,0 +1,142 @@
+use gq_core::query::{apply::Error, query_operators, Query};
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::{ai_models, programming_languages};
+
+#[rstest]
+fn group_by(programming_languages: Value) {
+    let query: Query = "languages[groupBy(year)]".parse().unwrap();
+    let expected = json!({
+        "1995": [
+            { "name": "JavaScript", "popular": true, "year": 1995 },
+            { "name": "Java", "popular": false, "year": 1995 }
+        ],
+        "2010": [
+            { "name": "Rust", "popular": true, "year": 2010 }
+        ]
+    });
+
+    let result = query.apply(programming_languages).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn group_by_with_children(programming_languages: Value) {
+    let query: Query = "languages[groupBy(popular)] { name }".parse().unwrap();
+    let expected = json!({
+        "true": [{ "name": "JavaScript" }, { "name": "Rust" }],
+        "false": [{ "name": "Java" }]
+    });
+
+    let result = query.apply(programming_languages).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn group_by_with_filter_and_sort(programming_languages: Value) {
+    let query: Query = r#"{
+        languages(popular = true)[sort(name desc)][groupBy(year)]: byYear {
+            name
+        }
+    }"#
+    .parse()
+    .unwrap();
+    let expected = json!({
+        "byYear": {
+            "2010": [{ "name": "Rust" }],
+            "1995": [{ "name": "JavaScript" }]
+        }
+    });
+
+    let result = query.apply(programming_languages).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn group_by_nested_key(ai_models: Value) {
+    let query: Query = "models[groupBy(tags[0])] { name }".parse().unwrap();
+    let expected = json!({
+        "NLP": [{ "name": "GPT-4O" }],
+        "null": [{ "name": "Claude" }],
+        "Text Generation": [{ "name": "LLAMA" }]
+    });
+
+    let result = query.apply(ai_models).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn group_by_missing_key_as_null() {
+    let value = json!({
+        "events": [
+            { "service": "api", "message": "timeout" },
+            { "message": "unknown" },
+            { "service": "db", "message": "deadlock" },
+            { "service": "api", "message": "bad gateway" }
+        ]
+    });
+    let query: Query = "events[groupBy(service)] { message }".parse().unwrap();
+    let expected = json!({
+        "api": [{ "message": "timeout" }, { "message": "bad gateway" }],
+        "null": [{ "message": "unknown" }],
+        "db": [{ "message": "deadlock" }]
+    });
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn group_by_inside_array() {
+    let value = json!({
+        "services": [
+            {
+                "name": "api",
+                "events": [
+                    { "level": "error", "message": "timeout" },
+                    { "level": "info", "message": "started" }
+                ]
+            },
+            {
+                "name": "db",
+                "events": [{ "level": "error", "message": "deadlock" }]
+            }
+        ]
+    });
+    let query: Query = "services.events[groupBy(level)] { message }"
+        .parse()
+        .unwrap();
+    let expected = json!([
+        {
+            "error": [{ "message": "timeout" }],
+            "info": [{ "message": "started" }]
+        },
+        {
+            "error": [{ "message": "deadlock" }]
+        }
+    ]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn unsupported_type(programming_languages: Value) {
+    let query: Query = "category[groupBy(name)]".parse().unwrap();
+
+    let result = query.apply(programming_languages);
+
+    assert!(matches!(
+        result,
+        Err(Error::QueryOperatorError(
+            query_operators::Error::UnsupportedType { .. }
+        ))
+    ));
+}
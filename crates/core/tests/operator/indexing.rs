@@ -1,8 +1,12 @@
-use gq_core::query::Query;
+use gq_core::query::{
+    apply::{ApplyOptions, Error},
+    query_operators::{self, OutOfBounds},
+    Query,
+};
 use rstest::rstest;
 use serde_json::{json, Value};
 
-use crate::fixtures::products;
+use crate::fixtures::{ai_models, products};
 
 #[rstest]
 fn single_indexing(products: Value) {
@@ -39,6 +43,67 @@ fn range_indexing(products: Value) {
     assert_eq!(result, expected);
 }
 
+#[rstest]
+fn negative_indexing(products: Value) {
+    let query: Query = "products[-1].name".parse().unwrap();
+    let expected = json!("Product 3");
+
+    let result = query.apply(products).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+#[case::inclusive_range("products[0..=1].name", json!(["Product 1", "Product 2"]))]
+#[case::exclusive_range("products[0..<1].name", json!(["Product 1"]))]
+#[case::exclusive_range_with_negative_end("products[0..<-1].name", json!(["Product 1", "Product 2"]))]
+#[case::negative_start_range("products[-2..].name", json!(["Product 2", "Product 3"]))]
+#[case::negative_range("products[-3..-2].name", json!(["Product 1", "Product 2"]))]
+#[case::open_end_range("products[1..].name", json!(["Product 2", "Product 3"]))]
+#[case::open_start_range("products[..1].name", json!(["Product 1", "Product 2"]))]
+#[case::full_range("products[..].name", json!(["Product 1", "Product 2", "Product 3"]))]
+#[case::empty_range("products[1..<1].name", json!([]))]
+#[case::reversed_range("products[2..0].name", json!([]))]
+#[case::reversed_negative_range("products[-1..<-2].name", json!([]))]
+fn range_bounds_indexing(products: Value, #[case] query: &str, #[case] expected: Value) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(products).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+#[case::step("[0..9:3]", json!([0, 3, 6, 9]))]
+#[case::step_bigger_than_range("[1..3:10]", json!([1]))]
+#[case::open_range_step("[..:4]", json!([0, 4, 8]))]
+#[case::reverse("[..:-1]", json!([9, 8, 7, 6, 5, 4, 3, 2, 1, 0]))]
+#[case::reverse_shorthand("[::-1]", json!([9, 8, 7, 6, 5, 4, 3, 2, 1, 0]))]
+#[case::negative_step("[0..<5:-2]", json!([4, 2, 0]))]
+#[case::negative_range_step("[-4..:2]", json!([6, 8]))]
+#[case::descending("[4..0:-1]", json!([4, 3, 2, 1, 0]))]
+#[case::descending_exclusive("[4..<0:-2]", json!([4, 2]))]
+#[case::descending_negative("[-1..-3:-1]", json!([9, 8, 7]))]
+#[case::descending_positive_step("[4..0:1]", json!([]))]
+fn stepped_range_indexing(#[case] query: &str, #[case] expected: Value) {
+    let value = json!([0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn stepped_range_indexing_and_accessing(products: Value) {
+    let query: Query = "products[::-2].name".parse().unwrap();
+    let expected = json!(["Product 3", "Product 1"]);
+
+    let result = query.apply(products).unwrap();
+
+    assert_eq!(result, expected);
+}
+
 #[rstest]
 fn consecutive_single_indexing() {
     let value = json!({
@@ -163,3 +228,69 @@ fn unsupported_type_inside_array(products: Value) {
 
     assert_eq!(result, expected);
 }
+
+#[rstest]
+#[case::index("products[3]")]
+#[case::negative_index("products[-4]")]
+#[case::range("products[1..3]")]
+#[case::negative_range("products[-4..]")]
+#[case::stepped_range("products[1..3:2]")]
+fn out_of_bounds_error(products: Value, #[case] query: &str) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(products);
+
+    assert!(matches!(
+        result,
+        Err(Error::QueryOperatorError(
+            query_operators::Error::IndexOutOfBounds { length: 3, .. }
+        ))
+    ));
+}
+
+#[rstest]
+#[case::index("products[3]")]
+#[case::range("products[1..3]")]
+fn out_of_bounds_null(products: Value, #[case] query: &str) {
+    let query: Query = query.parse().unwrap();
+    let options = ApplyOptions {
+        out_of_bounds: OutOfBounds::Null,
+        ..Default::default()
+    };
+
+    let result = query.apply_with_options(products, options).unwrap();
+
+    assert_eq!(result, Value::Null);
+}
+
+#[rstest]
+#[case::index("products[3].name", json!(null))]
+#[case::range("products[1..10].name", json!(["Product 2", "Product 3"]))]
+#[case::exclusive_range("products[..<10].name", json!(["Product 1", "Product 2", "Product 3"]))]
+#[case::negative_range("products[-10..0].name", json!(["Product 1"]))]
+#[case::negative_end_range("products[..-10].name", json!([]))]
+#[case::reversed_range("products[2..1].name", json!([]))]
+#[case::range_after_end("products[5..].name", json!([]))]
+#[case::stepped_range("products[..10:-2].name", json!(["Product 3", "Product 1"]))]
+fn out_of_bounds_clamp(products: Value, #[case] query: &str, #[case] expected: Value) {
+    let query: Query = query.parse().unwrap();
+    let options = ApplyOptions {
+        out_of_bounds: OutOfBounds::Clamp,
+        ..Default::default()
+    };
+
+    let result = query.apply_with_options(products, options).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+// TODO: assert that a warning is logged with the proper message
+#[rstest]
+fn out_of_bounds_inside_array(ai_models: Value) {
+    let query: Query = "models.tags[1]".parse().unwrap();
+    let expected = json!(["Text Generation", "Open Source"]);
+
+    let result = query.apply(ai_models).unwrap();
+
+    assert_eq!(result, expected);
+}
@@ -1 +1,6 @@
+mod aggregation;
+mod flatten;
+mod group_by;
 mod indexing;
+mod sort;
+mod unique;
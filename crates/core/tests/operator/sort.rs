@@ -0,0 +1,113 @@
+use gq_core::query::{apply::Error, query_operators, Query};
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::{ai_models, products};
+
+#[rstest]
+#[case::ascending("products[sort(price)].name", json!(["Product 1", "Product 2", "Product 3"]))]
+#[case::explicit_ascending("products[sort(quantity asc)].name", json!(["Product 3", "Product 2", "Product 1"]))]
+#[case::descending("products[sort(price desc)].name", json!(["Product 3", "Product 2", "Product 1"]))]
+#[case::sort_then_index("products[sort(price desc)][0].name", json!("Product 3"))]
+#[case::sort_then_range("products[sort(quantity)][0..1].name", json!(["Product 3", "Product 2"]))]
+fn sort_by_key(products: Value, #[case] query: &str, #[case] expected: Value) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(products).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn sort_by_multiple_keys() {
+    let value = json!({ "items": [
+        { "name": "b", "year": 1995 },
+        { "name": "c", "year": 2010 },
+        { "name": "a", "year": 1995 },
+        { "name": "d", "year": 2010 }
+    ]});
+    let query: Query = "items[sort(year desc, name)].name".parse().unwrap();
+    let expected = json!(["c", "d", "a", "b"]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn sort_by_nested_key() {
+    let value = json!({ "items": [
+        { "id": 1, "stats": { "score": 3 } },
+        { "id": 2, "stats": { "score": 1 } },
+        { "id": 3, "stats": { "score": 2 } }
+    ]});
+    let query: Query = "items[sort(stats.score)].id".parse().unwrap();
+    let expected = json!([2, 3, 1]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn sort_is_stable() {
+    let value = json!({ "items": [
+        { "id": 1, "group": "b" },
+        { "id": 2, "group": "a" },
+        { "id": 3, "group": "b" },
+        { "id": 4, "group": "a" }
+    ]});
+    let query: Query = "items[sort(group)].id".parse().unwrap();
+    let expected = json!([2, 4, 1, 3]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn sort_primitives() {
+    let value = json!({ "numbers": [3, -1.5, 10, 2] });
+    let query: Query = "numbers[sort]".parse().unwrap();
+    let expected = json!([-1.5, 2, 3, 10]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn sort_mixed_types() {
+    let value = json!([{ "a": 1 }, "b", [1], 2, true, null, "a", false]);
+    let query: Query = "[sort]".parse().unwrap();
+    let expected = json!([null, false, true, 2, "a", "b", [1], { "a": 1 }]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+#[case::ascending("models[sort(score)].name", json!(["Claude", "GPT-4O", "LLAMA"]))]
+#[case::descending("models[sort(tags desc)].name", json!(["LLAMA", "GPT-4O", "Claude"]))]
+fn sort_missing_keys_as_null(ai_models: Value, #[case] query: &str, #[case] expected: Value) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(ai_models).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn unsupported_type(products: Value) {
+    let query: Query = "id[sort]".parse().unwrap();
+
+    let result = query.apply(products);
+
+    assert!(matches!(
+        result,
+        Err(Error::QueryOperatorError(
+            query_operators::Error::UnsupportedType { .. }
+        ))
+    ));
+}
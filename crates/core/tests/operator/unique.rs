@@ -0,0 +1,86 @@
+use gq_core::query::{apply::Error, query_operators, Query};
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use crate::fixtures::programming_languages;
+
+#[rstest]
+#[case::primitives("years[unique]", json!({ "years": [1995, 2010, 1995, 2010, 2024] }), json!([1995, 2010, 2024]))]
+#[case::mixed_types("values[unique]", json!({ "values": [1, "1", null, 1, null, true] }), json!([1, "1", null, true]))]
+#[case::objects_with_different_key_order(
+    "values[unique]",
+    json!({ "values": [{ "a": 1, "b": 2 }, { "b": 2, "a": 1 }, { "a": 2 }] }),
+    json!([{ "a": 1, "b": 2 }, { "a": 2 }])
+)]
+#[case::numbers("values[unique]", json!({ "values": [1, 1.0, 2.5, 2.50] }), json!([1, 2.5]))]
+#[case::arrays("values[unique]", json!({ "values": [[1, 2], [2, 1], [1, 2]] }), json!([[1, 2], [2, 1]]))]
+#[case::empty("values[unique]", json!({ "values": [] }), json!([]))]
+fn unique(#[case] query: &str, #[case] value: Value, #[case] expected: Value) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn unique_inside_array(programming_languages: Value) {
+    let query: Query = "languages.year[unique]".parse().unwrap();
+    let expected = json!([1995, 2010]);
+
+    let result = query.apply(programming_languages).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn unique_then_count(programming_languages: Value) {
+    let query: Query = "languages.popular[unique][count]".parse().unwrap();
+
+    let result = query.apply(programming_languages).unwrap();
+
+    assert_eq!(result, json!(2));
+}
+
+#[rstest]
+fn unique_by(programming_languages: Value) {
+    let query: Query = "languages[uniqueBy(year)].name".parse().unwrap();
+    let expected = json!(["JavaScript", "Rust"]);
+
+    let result = query.apply(programming_languages).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn unique_by_nested_key() {
+    let value = json!({
+        "stores": [
+            { "id": 1, "address": { "region": "north" } },
+            { "id": 2, "address": { "region": "south" } },
+            { "id": 3 },
+            { "id": 4, "address": { "region": "north" } },
+            { "id": 5 }
+        ]
+    });
+    let query: Query = "stores[uniqueBy(address.region)].id".parse().unwrap();
+    let expected = json!([1, 2, 3]);
+
+    let result = query.apply(value).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn unsupported_type(programming_languages: Value) {
+    let query: Query = "category[unique]".parse().unwrap();
+
+    let result = query.apply(programming_languages);
+
+    assert!(matches!(
+        result,
+        Err(Error::QueryOperatorError(
+            query_operators::Error::UnsupportedType { .. }
+        ))
+    ));
+}
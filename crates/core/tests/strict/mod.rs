@@ -0,0 +1,83 @@
+use gq_core::query::{
+    apply::{ApplyOptions, Error},
+    Query,
+};
+use rstest::{fixture, rstest};
+use serde_json::{json, Value};
+
+use crate::fixtures::{ai_models, customers};
+
+#[fixture]
+fn strict() -> ApplyOptions {
+    ApplyOptions {
+        strict: true,
+        ..Default::default()
+    }
+}
+
+#[rstest]
+#[case::key_inside_array("customers.email")]
+#[case::children_inside_array("customers { name email }")]
+#[case::wildcard("regions.*.sales")]
+#[case::wildcard_children("regions.* { sales }")]
+fn strict_fails_inside_array(customers: Value, strict: ApplyOptions, #[case] query: &str) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply_with_options(customers, strict);
+
+    assert!(matches!(result, Err(Error::InsideArray(..))));
+}
+
+#[rstest]
+fn strict_fails_on_out_of_bounds_inside_array(ai_models: Value, strict: ApplyOptions) {
+    let query: Query = "models.tags[1]".parse().unwrap();
+
+    let result = query.apply_with_options(ai_models, strict);
+
+    assert!(matches!(result, Err(Error::InsideArray(..))));
+}
+
+#[rstest]
+fn strict_error_contains_the_cause(customers: Value, strict: ApplyOptions) {
+    let query: Query = "customers.email".parse().unwrap();
+
+    let result = query.apply_with_options(customers, strict);
+
+    let Err(Error::InsideArray(cause, path)) = result else {
+        panic!("expected an error inside an array, got {result:?}");
+    };
+    assert!(matches!(*cause, Error::KeyNotFound(_)));
+    assert_eq!(path.to_string(), ".customers");
+}
+
+#[rstest]
+#[case::optional_key("customers.email?", json!(["alice@example.com", null, null]))]
+#[case::default_value(
+    "customers { name email ?? \"none\" }",
+    json!([
+        { "name": "Alice", "email": "alice@example.com" },
+        { "name": "Bob", "email": "none" },
+        { "name": "Carol", "email": "none" }
+    ])
+)]
+fn strict_succeeds_without_errors(
+    customers: Value,
+    strict: ApplyOptions,
+    #[case] query: &str,
+    #[case] expected: Value,
+) {
+    let query: Query = query.parse().unwrap();
+
+    let result = query.apply_with_options(customers, strict).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[rstest]
+fn non_strict_discards_elements(customers: Value) {
+    let query: Query = "customers.email".parse().unwrap();
+
+    let result = query.apply(customers).unwrap();
+
+    assert_eq!(result, json!(["alice@example.com", null]));
+}
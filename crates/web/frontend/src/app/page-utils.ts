@@ -2,6 +2,7 @@ import { notify } from "@/lib/notify";
 import type { Completion } from "@/model/completion";
 import { Data } from "@/model/data";
 import FileType from "@/model/file-type";
+import type { QueryResult } from "@/model/query-result";
 import { getShare } from "@/services/share/share-service";
 import type { CompletionContext, CompletionSource } from "@codemirror/autocomplete";
 import nunjucks from "nunjucks";
@@ -14,8 +15,8 @@ export const applyGq = async (
 	indent: number,
 	gqWorker: PromiseWorker,
 	silent = true,
-): Promise<Data> => {
-	const result: Data = await gqWorker.postMessage({
+): Promise<QueryResult> => {
+	const result: QueryResult = await gqWorker.postMessage({
 		query: queryContent,
 		data: inputData,
 		outputType: outputType,
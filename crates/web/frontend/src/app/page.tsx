@@ -159,7 +159,8 @@ const Home = () => {
 				);
 				addNewQueryCallback.current(queryContent);
 				setErrorMessage(undefined);
-				updateOutputEditorCallback.current(result);
+				setWarningMessages(result.warnings);
+				updateOutputEditorCallback.current(result.data);
 				handleChangeTemplateContent(jinjaContent.current);
 			} catch (err) {
 				setErrorMessage(err.message);
@@ -0,0 +1,6 @@
+import type { Data } from "./data";
+
+export type QueryResult = {
+	data: Data;
+	warnings: string[];
+};
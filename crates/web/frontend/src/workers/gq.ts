@@ -1,6 +1,13 @@
 import type { Data } from "@/model/data";
 import type FileType from "@/model/file-type";
-import init, { type JsData, gq } from "gq-web";
+import type { QueryResult } from "@/model/query-result";
+import init, {
+	JsApplyOptions,
+	JsEmptyElements,
+	JsOutOfBounds,
+	type JsQueryOutput,
+	gq,
+} from "gq-web";
 import registerWebworker from "webworker-promise/lib/register";
 import { dataToDto, dataToModel } from "./dtos/data-dto";
 import { fileTypeToDto } from "./dtos/file-type-dto";
@@ -10,10 +17,37 @@ interface Message {
 	data: Data;
 	outputType: FileType;
 	indent: number;
+	outOfBounds?: JsOutOfBounds;
+	emptyElements?: JsEmptyElements;
+	strict?: boolean;
 }
 
-registerWebworker(async ({ query, data, outputType, indent }: Message): Promise<Data> => {
-	await init();
-	const result: JsData = gq(query, dataToDto(data), fileTypeToDto(outputType), indent);
-	return dataToModel(result);
-});
+registerWebworker(
+	async ({
+		query,
+		data,
+		outputType,
+		indent,
+		outOfBounds,
+		emptyElements,
+		strict,
+	}: Message): Promise<QueryResult> => {
+		await init();
+		const options = new JsApplyOptions(
+			outOfBounds ?? JsOutOfBounds.Error,
+			emptyElements ?? JsEmptyElements.Remove,
+			strict ?? false,
+		);
+		const result: JsQueryOutput = gq(
+			query,
+			dataToDto(data),
+			fileTypeToDto(outputType),
+			indent,
+			options,
+		);
+		const warnings = result.diagnostics
+			.filter((diagnostic) => diagnostic.level === "warn")
+			.map((diagnostic) => diagnostic.message);
+		return { data: dataToModel(result.data), warnings };
+	},
+);
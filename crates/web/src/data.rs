@@ -20,7 +20,7 @@ impl From<JsDataType> for DataType {
 }
 
 #[wasm_bindgen(getter_with_clone)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct JsData {
     pub payload: String,
     pub data_type: JsDataType,
@@ -0,0 +1,35 @@
+use gq_core::query::diagnostic::Diagnostic;
+use wasm_bindgen::prelude::*;
+
+use crate::data::JsData;
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct JsDiagnostic {
+    pub kind: String,
+    pub level: String,
+    pub path: String,
+    pub message: String,
+    pub span_start: Option<usize>,
+    pub span_end: Option<usize>,
+}
+
+impl From<Diagnostic> for JsDiagnostic {
+    fn from(diagnostic: Diagnostic) -> Self {
+        Self {
+            kind: diagnostic.kind().to_string(),
+            level: diagnostic.kind().level().as_str().to_lowercase(),
+            path: diagnostic.path().to_string(),
+            message: diagnostic.message().clone(),
+            span_start: diagnostic.span().as_ref().map(|span| span.start),
+            span_end: diagnostic.span().as_ref().map(|span| span.end),
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug)]
+pub struct JsQueryOutput {
+    pub data: JsData,
+    pub diagnostics: Vec<JsDiagnostic>,
+}
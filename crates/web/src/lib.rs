@@ -7,9 +7,13 @@ use serde_json::Value;
 use wasm_bindgen::prelude::*;
 
 use crate::data::JsData;
+use crate::diagnostic::JsQueryOutput;
+use crate::options::JsApplyOptions;
 
 pub mod data;
+pub mod diagnostic;
 pub mod lsp;
+pub mod options;
 
 #[wasm_bindgen]
 pub fn gq(
@@ -17,16 +21,20 @@ pub fn gq(
     data: JsData,
     output_type: JsDataType,
     indent: usize,
-) -> Result<JsData, JsError> {
+    options: JsApplyOptions,
+) -> Result<JsQueryOutput, JsError> {
     let query = query.parse::<Query>()?;
     let core_data = Data::from(data);
     let value = Value::try_from(&core_data)?;
     let indentation = Indentation::with_spaces(indent);
 
-    let result = query.apply(value)?;
-    let output_data = Data::pretty_from_value(&result, output_type.into(), indentation)?;
+    let output = query.apply_with_diagnostics(value, options.into())?;
+    let output_data = Data::pretty_from_value(&output.value, output_type.into(), indentation)?;
 
-    Ok(output_data.into())
+    Ok(JsQueryOutput {
+        data: output_data.into(),
+        diagnostics: output.diagnostics.into_iter().map(From::from).collect(),
+    })
 }
 
 #[wasm_bindgen]
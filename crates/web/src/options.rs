@@ -0,0 +1,77 @@
+use gq_core::query::{
+    apply::{ApplyOptions, EmptyElements},
+    query_operators::OutOfBounds,
+};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, Default)]
+pub enum JsOutOfBounds {
+    #[default]
+    Error,
+    Null,
+    Clamp,
+}
+
+impl From<JsOutOfBounds> for OutOfBounds {
+    fn from(js_out_of_bounds: JsOutOfBounds) -> Self {
+        match js_out_of_bounds {
+            JsOutOfBounds::Error => OutOfBounds::Error,
+            JsOutOfBounds::Null => OutOfBounds::Null,
+            JsOutOfBounds::Clamp => OutOfBounds::Clamp,
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, Default)]
+pub enum JsEmptyElements {
+    #[default]
+    Remove,
+    Keep,
+    Null,
+}
+
+impl From<JsEmptyElements> for EmptyElements {
+    fn from(js_empty_elements: JsEmptyElements) -> Self {
+        match js_empty_elements {
+            JsEmptyElements::Remove => EmptyElements::Remove,
+            JsEmptyElements::Keep => EmptyElements::Keep,
+            JsEmptyElements::Null => EmptyElements::Null,
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsApplyOptions {
+    pub out_of_bounds: JsOutOfBounds,
+    pub empty_elements: JsEmptyElements,
+    pub strict: bool,
+}
+
+#[wasm_bindgen]
+impl JsApplyOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        out_of_bounds: JsOutOfBounds,
+        empty_elements: JsEmptyElements,
+        strict: bool,
+    ) -> Self {
+        Self {
+            out_of_bounds,
+            empty_elements,
+            strict,
+        }
+    }
+}
+
+impl From<JsApplyOptions> for ApplyOptions {
+    fn from(js_options: JsApplyOptions) -> Self {
+        Self {
+            out_of_bounds: js_options.out_of_bounds.into(),
+            empty_elements: js_options.empty_elements.into(),
+            strict: js_options.strict,
+        }
+    }
+}